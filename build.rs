@@ -0,0 +1,125 @@
+//  	Build script
+//	Generates the geometric algebra product tables from their signatures; see
+//	`codegen/algebra.rs` for how the tables are derived and `codegen/emit.rs` for
+//	the Rust they turn into. The output lands in `OUT_DIR` and is pulled in with
+//	`include!` by the algebra's modules. The generator's own tests run with the
+//	library's (see `src/lib.rs`).
+use std::{env, fs, path::Path};
+
+//	Not every signature uses every part of the generator
+#[allow(dead_code)] #[path = "codegen/algebra.rs"] mod algebra;
+#[allow(dead_code)] #[path = "codegen/emit.rs"] mod emit;
+
+use algebra::{Algebra, Duality, Product::*};
+use emit::{Element, TypedProduct, TypedSandwich, SandwichPart};
+
+//		PGA3D
+//	R(3,0,1) in the bivector.net basis: e0 is null, and each blade's dual is its
+//	mirror in the list.
+const PGA3D_METRIC: &[i8] = &[0, 1, 1, 1];
+const PGA3D_BASIS: &[&str] = &[
+	"1", "e0", "e1", "e2", "e3", "e01", "e02", "e03", "e12", "e31", "e23", "e021", "e013", "e032", "e123", "e0123",
+];
+
+const PGA3D_ELEMENTS: &[Element] = &[
+	Element { name: "Plane", blades: &["e0", "e1", "e2", "e3"] },
+	Element { name: "Line", blades: &["e01", "e02", "e03", "e12", "e31", "e23"] },
+	Element { name: "Point", blades: &["e021", "e013", "e032", "e123"] },
+	Element { name: "Motor", blades: &["1", "e01", "e02", "e03", "e12", "e31", "e23", "e0123"] },
+	Element { name: "Rotor", blades: &["1", "e12", "e31", "e23"] },
+	Element { name: "Translator", blades: &["1", "e01", "e02", "e03"] },
+];
+
+macro_rules! products {
+	($($product:ident: $($lhs:ident $rhs:ident => $out:ident),*;)*) => {
+		&[$($(TypedProduct { product: $product, lhs: stringify!($lhs), rhs: stringify!($rhs), out: stringify!($out) },)*)*]
+	};
+}
+
+const PGA3D_PRODUCTS: &[TypedProduct] = products! {
+	Geometric:
+		Motor Motor => Motor, Motor Rotor => Motor, Rotor Motor => Motor,
+		Motor Translator => Motor, Translator Motor => Motor,
+		Rotor Rotor => Rotor, Translator Translator => Translator,
+		Rotor Translator => Motor, Translator Rotor => Motor,
+		Plane Plane => Motor, Line Line => Motor, Point Point => Translator;
+	Wedge:
+		Plane Plane => Line, Plane Line => Point, Line Plane => Point;
+	Vee:
+		Point Point => Line, Point Line => Plane, Line Point => Plane;
+	Dot:
+		Plane Plane => S, Line Line => S, Point Point => S,
+		Plane Line => Plane, Line Plane => Plane, Point Line => Plane, Line Point => Plane,
+		Plane Point => Line, Point Plane => Line;
+};
+
+const PGA3D_SANDWICHES: &[TypedSandwich] = &[
+	TypedSandwich { versor: "Motor", element: "Point", part: None },
+	TypedSandwich { versor: "Motor", element: "Line", part: None },
+	TypedSandwich { versor: "Motor", element: "Plane", part: None },
+	TypedSandwich { versor: "Rotor", element: "Point", part: None },
+	TypedSandwich { versor: "Rotor", element: "Line", part: None },
+	TypedSandwich { versor: "Rotor", element: "Plane", part: None },
+	TypedSandwich { versor: "Translator", element: "Point", part: None },
+	TypedSandwich { versor: "Translator", element: "Line", part: None },
+	TypedSandwich { versor: "Translator", element: "Plane", part: None },
+	//	Directions turn with the rotation part alone: no translation, no weight
+	TypedSandwich { versor: "Motor", element: "Point", part: Some(SandwichPart {
+		method: "apply_direction", versor: &["1", "e12", "e31", "e23"], element: &["e021", "e013", "e032"],
+	}) },
+];
+
+//		PGA2D
+//	R(2,0,1), again in the bivector.net basis: lines are vectors, points bivectors.
+const PGA2D_METRIC: &[i8] = &[0, 1, 1];
+const PGA2D_BASIS: &[&str] = &["1", "e0", "e1", "e2", "e01", "e20", "e12", "e012"];
+
+const PGA2D_ELEMENTS: &[Element] = &[
+	Element { name: "Line", blades: &["e0", "e1", "e2"] },
+	Element { name: "Point", blades: &["e01", "e20", "e12"] },
+	Element { name: "Motor", blades: &["1", "e01", "e20", "e12"] },
+	Element { name: "Rotor", blades: &["1", "e12"] },
+	Element { name: "Translator", blades: &["1", "e01", "e20"] },
+];
+
+const PGA2D_PRODUCTS: &[TypedProduct] = products! {
+	Geometric:
+		Motor Motor => Motor, Motor Rotor => Motor, Rotor Motor => Motor,
+		Motor Translator => Motor, Translator Motor => Motor,
+		Rotor Rotor => Rotor, Translator Translator => Translator,
+		Rotor Translator => Motor, Translator Rotor => Motor,
+		Line Line => Motor, Point Point => Translator;
+	Wedge:
+		Line Line => Point;
+	Vee:
+		Point Point => Line;
+	Dot:
+		Line Line => S, Point Point => S, Point Line => Line, Line Point => Line;
+};
+
+const PGA2D_SANDWICHES: &[TypedSandwich] = &[
+	TypedSandwich { versor: "Motor", element: "Point", part: None },
+	TypedSandwich { versor: "Motor", element: "Line", part: None },
+	TypedSandwich { versor: "Rotor", element: "Point", part: None },
+	TypedSandwich { versor: "Rotor", element: "Line", part: None },
+	TypedSandwich { versor: "Translator", element: "Point", part: None },
+	TypedSandwich { versor: "Translator", element: "Line", part: None },
+];
+
+fn main() {
+	//	Relative to the package root, wherever this script is built from
+	let here = Path::new(file!()).parent().unwrap_or(Path::new(""));
+	println!("cargo::rerun-if-changed={}", here.join("build.rs").display());
+	println!("cargo::rerun-if-changed={}", here.join("codegen").display());
+
+	let out = Path::new(&env::var_os("OUT_DIR").expect("cargo sets OUT_DIR")).to_path_buf();
+	let write = |file: &str, code: String| fs::write(out.join(file), code).expect("OUT_DIR is writable");
+
+	let pga3d = Algebra::new(PGA3D_METRIC, PGA3D_BASIS, Duality::Mirror).unwrap_or_else(|e| panic!("PGA3D: {}", e));
+	write("pga3d_ops.rs", emit::multivector(&pga3d, "PGA3D"));
+	write("pga3d_elements.rs", emit::elements(&pga3d, "define_pga_type", "PGA3D", PGA3D_ELEMENTS, PGA3D_PRODUCTS, PGA3D_SANDWICHES));
+
+	let pga2d = Algebra::new(PGA2D_METRIC, PGA2D_BASIS, Duality::Mirror).unwrap_or_else(|e| panic!("PGA2D: {}", e));
+	write("pga2d_ops.rs", emit::multivector(&pga2d, "PGA2D"));
+	write("pga2d_elements.rs", emit::elements(&pga2d, "define_pga_type", "PGA2D", PGA2D_ELEMENTS, PGA2D_PRODUCTS, PGA2D_SANDWICHES));
+}
@@ -0,0 +1,386 @@
+//  	Imports
+use std::fmt;
+
+//		Geometric algebra from a signature
+//	Everything the emitters need is derived here from two inputs: the square of
+//	each basis vector and the list of basis blades in storage order. Blade names
+//	carry their orientation ("e021" is e0^e2^e1 = -e012), so any signature and
+//	any choice of basis (bivector.net's PGA layouts, CGA, ...) is described the
+//	same way and every table entry is a product of ±1 coefficients.
+
+//	How the dual (and with it the regressive product) is formed
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Duality {
+	//	Poincaré duality: the basis is laid out so that each blade's complement is
+	//	its mirror image in the list, and the dual just reverses the coefficients.
+	//	Works for degenerate metrics (PGA), where no pseudoscalar inverse exists.
+	Mirror,
+	//	Right multiplication by the inverse pseudoscalar, for non-degenerate metrics
+	Pseudoscalar,
+}
+
+#[derive(Clone, Debug)]
+pub struct Blade {
+	pub name: &'static str,
+	pub mask: u32,
+	//	Orientation relative to the ascending product of its basis vectors
+	pub sign: i8,
+}
+
+impl Blade {
+	pub fn grade(&self) -> usize { self.mask.count_ones() as usize }
+
+	//	Field name in the typed elements; the scalar is `s`
+	pub fn field(&self) -> &'static str { if self.mask == 0 { "s" } else { self.name } }
+}
+
+//	A term c·a[i]·b[j] of a bilinear product (or c·a[i] of a unary one)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Term {
+	pub coeff: i8,
+	pub a: usize,
+	pub b: usize,
+}
+
+//	The four bilinear products, by operator
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Product {
+	Geometric,
+	Wedge,
+	Vee,
+	Dot,
+}
+
+impl Product {
+	//	Operator trait and method implementing the product
+	pub fn op(self) -> (&'static str, &'static str) {
+		match self {
+			Product::Geometric => ("Mul", "mul"),
+			Product::Wedge => ("BitXor", "bitxor"),
+			Product::Vee => ("BitAnd", "bitand"),
+			Product::Dot => ("BitOr", "bitor"),
+		}
+	}
+
+	//	Inherent method computing the product on a full multivector
+	pub fn method(self) -> &'static str {
+		match self {
+			Product::Geometric => "geometric",
+			Product::Wedge => "wedge",
+			Product::Vee => "vee",
+			Product::Dot => "dot",
+		}
+	}
+}
+
+pub struct Algebra {
+	pub metric: Vec<i8>,
+	pub blades: Vec<Blade>,
+	pub duality: Duality,
+}
+
+#[derive(Debug)]
+pub struct SignatureError(String);
+
+impl fmt::Display for SignatureError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result { write!(f, "invalid signature: {}", self.0) }
+}
+
+impl Algebra {
+	//	`metric[i]` is the square of basis vector ei (0, 1 or -1);
+	//	`basis` names every blade once as "1" or "e" followed by its vector indices.
+	pub fn new(metric: &[i8], basis: &[&'static str], duality: Duality) -> Result<Self, SignatureError> {
+		let n = metric.len();
+		if let Some(m) = metric.iter().find(|m| !(-1..=1).contains(*m)) {
+			return Err(SignatureError(format!("basis vectors must square to 0 or ±1, not {}", m)));
+		}
+		if basis.len() != 1 << n {
+			return Err(SignatureError(format!("{} vectors span {} blades, {} given", n, 1 << n, basis.len())));
+		}
+
+		let mut blades: Vec<Blade> = Vec::with_capacity(basis.len());
+		for &name in basis {
+			let blade = parse_blade(name, n)?;
+			if let Some(dup) = blades.iter().find(|b| b.mask == blade.mask) {
+				return Err(SignatureError(format!("`{}` and `{}` are the same blade", dup.name, name)));
+			}
+			blades.push(blade);
+		}
+
+		let alg = Self { metric: metric.to_vec(), blades, duality };
+		match duality {
+			Duality::Mirror => {
+				let full = (1u32 << n) - 1;
+				for (i, b) in alg.blades.iter().enumerate() {
+					let mirror = &alg.blades[alg.len() - 1 - i];
+					if b.mask ^ mirror.mask != full {
+						return Err(SignatureError(format!("`{}` is not the complement of `{}`", mirror.name, b.name)));
+					}
+				}
+			}
+			Duality::Pseudoscalar => {
+				if metric.contains(&0) {
+					return Err(SignatureError("a degenerate metric has no pseudoscalar inverse".into()));
+				}
+			}
+		}
+		Ok(alg)
+	}
+
+	pub fn len(&self) -> usize { self.blades.len() }
+
+	pub fn index(&self, mask: u32) -> usize {
+		self.blades.iter().position(|b| b.mask == mask).expect("every mask is a blade")
+	}
+
+	pub fn pseudoscalar(&self) -> usize { self.index((1 << self.metric.len()) - 1) }
+
+	//	Product of two basis blades as (coefficient, blade index); the coefficient
+	//	is zero when they share a null vector.
+	pub fn geometric(&self, i: usize, j: usize) -> (i8, usize) {
+		let (a, b) = (&self.blades[i], &self.blades[j]);
+		let mut sign = a.sign * b.sign;
+
+		//	Reorder the concatenated vectors into ascending order...
+		let mut swaps = 0;
+		let mut rest = a.mask >> 1;
+		while rest != 0 {
+			swaps += (rest & b.mask).count_ones();
+			rest >>= 1;
+		}
+		if !swaps.is_multiple_of(2) { sign = -sign; }
+
+		//	...and contract the vectors they have in common
+		let common = a.mask & b.mask;
+		for (v, &m) in self.metric.iter().enumerate() {
+			if common & (1 << v) != 0 { sign *= m; }
+		}
+
+		let k = self.index(a.mask ^ b.mask);
+		(sign * self.blades[k].sign, k)
+	}
+
+	//	Coefficient and blade of the dual of blade `i`
+	pub fn dual(&self, i: usize) -> (i8, usize) {
+		match self.duality {
+			Duality::Mirror => (1, self.len() - 1 - i),
+			Duality::Pseudoscalar => {
+				//	I^-1 = I / (I I), and I I = ±1 here
+				let ps = self.pseudoscalar();
+				let (square, _) = self.geometric(ps, ps);
+				let (c, k) = self.geometric(i, ps);
+				(c * square, k)
+			}
+		}
+	}
+
+	//	Complement the regressive product is taken through. For `Mirror` it is the
+	//	mirror blade signed so that conj(eA) ^ J(eA) = I, which keeps the pseudoscalar
+	//	the unit of the join; the unsigned mirror would flip the orientation of some
+	//	joins (e.g. of two points in PGA3D) and is only right for the `Dual` operator.
+	pub fn complement(&self, i: usize) -> (i8, usize) {
+		match self.duality {
+			Duality::Mirror => {
+				let k = self.len() - 1 - i;
+				let (c, _) = self.wedge(i, k);
+				(self.conjugate(i) * c, k)
+			}
+			Duality::Pseudoscalar => self.dual(i),
+		}
+	}
+
+	//	Inverse of `complement`
+	pub fn uncomplement(&self, i: usize) -> (i8, usize) {
+		(0..self.len())
+			.find_map(|k| match self.complement(k) { (c, d) if d == i => Some((c, k)), _ => None })
+			.expect("the complement is a permutation")
+	}
+
+	//	Grade involutions as a sign per blade
+	pub fn reverse(&self, i: usize) -> i8 {
+		let k = self.blades[i].grade();
+		if (k * k.saturating_sub(1) / 2).is_multiple_of(2) { 1 } else { -1 }
+	}
+
+	pub fn involute(&self, i: usize) -> i8 { if self.blades[i].grade().is_multiple_of(2) { 1 } else { -1 } }
+
+	pub fn conjugate(&self, i: usize) -> i8 { self.reverse(i) * self.involute(i) }
+
+	//	Terms of `product` contributing to each output blade, restricted to the
+	//	blades present in `lhs` and `rhs` and sorted by (a, b).
+	pub fn terms(&self, product: Product, lhs: &[usize], rhs: &[usize]) -> Vec<Vec<Term>> {
+		let mut out = vec![Vec::new(); self.len()];
+		for &i in lhs {
+			for &j in rhs {
+				let (c, k) = match product {
+					Product::Geometric => self.geometric(i, j),
+					Product::Wedge => self.wedge(i, j),
+					Product::Dot => self.dot(i, j),
+					Product::Vee => self.vee(i, j),
+				};
+				if c != 0 {
+					out[k].push(Term { coeff: c, a: i, b: j });
+				}
+			}
+		}
+		for terms in &mut out {
+			terms.sort_by_key(|t| (t.a, t.b));
+		}
+		out
+	}
+
+	//	Outer product: the grade ga + gb part of the geometric product
+	fn wedge(&self, i: usize, j: usize) -> (i8, usize) {
+		let (c, k) = self.geometric(i, j);
+		let keep = self.blades[k].grade() == self.blades[i].grade() + self.blades[j].grade();
+		(if keep { c } else { 0 }, k)
+	}
+
+	//	Inner product: the grade |ga - gb| part of the geometric product
+	fn dot(&self, i: usize, j: usize) -> (i8, usize) {
+		let (c, k) = self.geometric(i, j);
+		let keep = self.blades[k].grade() == self.blades[i].grade().abs_diff(self.blades[j].grade());
+		(if keep { c } else { 0 }, k)
+	}
+
+	//	Regressive product: J^-1(J(a) ^ J(b)) for the complement J
+	fn vee(&self, i: usize, j: usize) -> (i8, usize) {
+		let ((ca, da), (cb, db)) = (self.complement(i), self.complement(j));
+		let (cw, w) = self.wedge(da, db);
+		let (cu, k) = self.uncomplement(w);
+		(ca * cb * cw * cu, k)
+	}
+}
+
+fn parse_blade(name: &'static str, n: usize) -> Result<Blade, SignatureError> {
+	if name == "1" {
+		return Ok(Blade { name, mask: 0, sign: 1 });
+	}
+	let Some(digits) = name.strip_prefix('e') else {
+		return Err(SignatureError(format!("blade `{}` must start with `e`", name)));
+	};
+
+	let mut vectors = Vec::new();
+	for d in digits.chars() {
+		match d.to_digit(10) {
+			Some(v) if (v as usize) < n && !vectors.contains(&v) => vectors.push(v),
+			_ => return Err(SignatureError(format!("blade `{}` has a bad or repeated vector `{}`", name, d))),
+		}
+	}
+
+	//	Parity of the permutation that sorts the vectors
+	let mut inversions = 0;
+	for (p, a) in vectors.iter().enumerate() {
+		inversions += vectors[p + 1..].iter().filter(|b| *b < a).count();
+	}
+	let mask = vectors.iter().fold(0, |m, v| m | 1 << v);
+	Ok(Blade { name, mask, sign: if inversions.is_multiple_of(2) { 1 } else { -1 } })
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+	use super::*;
+
+	pub const PGA3D: &[&str] = &[
+		"1", "e0", "e1", "e2", "e3", "e01", "e02", "e03", "e12", "e31", "e23", "e021", "e013", "e032", "e123", "e0123",
+	];
+
+	//	Conformal R(4,1): e0..e3 square to one and e4 to minus one, so nothing is
+	//	null and the dual goes through the pseudoscalar
+	pub const CGA: &[&str] = &[
+		"1", "e0", "e1", "e2", "e3", "e4",
+		"e01", "e02", "e03", "e04", "e12", "e13", "e14", "e23", "e24", "e34",
+		"e012", "e013", "e014", "e023", "e024", "e034", "e123", "e124", "e134", "e234",
+		"e0123", "e0124", "e0134", "e0234", "e1234", "e01234",
+	];
+
+	pub fn pga3d() -> Algebra { Algebra::new(&[0, 1, 1, 1], PGA3D, Duality::Mirror).unwrap() }
+	pub fn cga() -> Algebra { Algebra::new(&[1, 1, 1, 1, -1], CGA, Duality::Pseudoscalar).unwrap() }
+
+	fn at(alg: &Algebra, name: &str) -> usize { alg.blades.iter().position(|b| b.name == name).unwrap() }
+
+	//		Tests
+	#[test] fn test_blades() {
+		let alg = pga3d();
+		let signs: Vec<i8> = ["e021", "e013", "e032", "e31"].iter().map(|&b| alg.blades[at(&alg, b)].sign).collect();
+		assert_eq!(signs, [-1, 1, -1, -1]);
+		assert_eq!(alg.blades[at(&alg, "e021")].mask, 0b0111);
+		assert_eq!(alg.blades[0].field(), "s");
+		assert_eq!(alg.pseudoscalar(), 15);
+	}
+
+	#[test] fn test_signature_errors() {
+		assert!(Algebra::new(&[0, 2], &["1", "e0", "e1", "e01"], Duality::Mirror).is_err());
+		assert!(Algebra::new(&[0, 1], &["1", "e0", "e1"], Duality::Mirror).is_err());
+		assert!(Algebra::new(&[0, 1], &["1", "e0", "e10", "e01"], Duality::Mirror).is_err());
+		assert!(Algebra::new(&[0, 1], &["1", "e0", "e2", "e01"], Duality::Mirror).is_err());
+		assert!(Algebra::new(&[0, 1], &["1", "e0", "e1", "x01"], Duality::Mirror).is_err());
+
+		//	Mirror needs complements mirrored, Pseudoscalar a non-degenerate metric
+		assert!(Algebra::new(&[0, 1], &["1", "e0", "e01", "e1"], Duality::Mirror).is_err());
+		assert!(Algebra::new(&[0, 1], &["1", "e0", "e1", "e01"], Duality::Mirror).is_ok());
+		assert!(Algebra::new(&[0, 1], &["1", "e0", "e1", "e01"], Duality::Pseudoscalar).is_err());
+	}
+
+	#[test] fn test_geometric() {
+		let alg = pga3d();
+		let g = |a: &str, b: &str| alg.geometric(at(&alg, a), at(&alg, b));
+		assert_eq!(g("e0", "e0").0, 0);
+		assert_eq!(g("e1", "e1"), (1, 0));
+		assert_eq!(g("e1", "e2"), (1, at(&alg, "e12")));
+		assert_eq!(g("e2", "e1"), (-1, at(&alg, "e12")));
+		assert_eq!(g("e12", "e12"), (-1, 0));
+		assert_eq!(g("e3", "e12"), (1, at(&alg, "e123")));
+		assert_eq!(g("e0123", "e0123").0, 0);
+
+		let alg = cga();
+		assert_eq!(alg.geometric(at(&alg, "e4"), at(&alg, "e4")), (-1, 0));
+		assert_eq!(alg.geometric(at(&alg, "e14"), at(&alg, "e14")), (1, 0));
+	}
+
+	#[test] fn test_terms() {
+		let alg = pga3d();
+		let plane: Vec<usize> = ["e0", "e1", "e2", "e3"].iter().map(|&b| at(&alg, b)).collect();
+		let terms = alg.terms(Product::Wedge, &plane, &plane);
+		assert_eq!(terms[at(&alg, "e12")], [Term { coeff: 1, a: 2, b: 3 }, Term { coeff: -1, a: 3, b: 2 }]);
+		assert!(terms[0].is_empty());
+		assert_eq!(alg.terms(Product::Dot, &plane, &plane)[0].len(), 3);
+	}
+
+	#[test] fn test_pga3d_duality() {
+		let alg = pga3d();
+		for i in 0..alg.len() {
+			//	The dual mirrors, the complement is a signed permutation, and the
+			//	pseudoscalar is the unit of the join
+			assert_eq!(alg.dual(i), (1, alg.len() - 1 - i));
+			let (c, k) = alg.complement(i);
+			assert_eq!(alg.uncomplement(k), (c, i));
+			assert_eq!(alg.vee(alg.pseudoscalar(), i), (1, i));
+		}
+
+		//	Joining the origin to the point at x = 1 gives the x axis
+		let p = |b: &str| at(&alg, b);
+		assert_eq!(alg.vee(p("e123"), p("e032")), (-1, p("e23")));
+	}
+
+	#[test] fn test_cga_duality() {
+		let alg = cga();
+		let ps = alg.pseudoscalar();
+		assert_eq!(alg.geometric(ps, ps), (-1, 0));
+
+		//	I⁻¹ = -I, so x I⁻¹ I⁻¹ = -x
+		assert_eq!(alg.dual(0), (-1, ps));
+		for i in 0..alg.len() {
+			let (c1, k) = alg.dual(i);
+			let (c2, j) = alg.dual(k);
+			assert_eq!((c1 * c2, j), (-1, i));
+			assert_eq!(alg.complement(i), alg.dual(i));
+			assert_eq!(alg.vee(ps, i), (1, i));
+		}
+
+		//	Two 4-blades meet in the blade they share
+		let b = |n: &str| at(&alg, n);
+		let (c, k) = alg.vee(b("e0123"), b("e1234"));
+		assert_eq!((c.abs(), k), (1, b("e123")));
+	}
+}
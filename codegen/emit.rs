@@ -0,0 +1,367 @@
+//  	Imports
+use std::fmt::Write;
+
+use super::algebra::{Algebra, Product, Term};
+
+//		Rust emitters
+//	All output is generic over `S: Real` and only ever adds, subtracts and
+//	negates products of coefficients: the tables are ±1, so no literal ever
+//	reaches the scalar type.
+
+//	A strongly typed element: its name and the blades it stores, in field order
+pub struct Element {
+	pub name: &'static str,
+	pub blades: &'static [&'static str],
+}
+
+//	`Lhs op Rhs -> Out`; an `Out` of "S" returns the bare scalar
+pub struct TypedProduct {
+	pub product: Product,
+	pub lhs: &'static str,
+	pub rhs: &'static str,
+	pub out: &'static str,
+}
+
+//	`Sandwich<Element> for Versor`: v x ~v, which keeps the element's type
+pub struct TypedSandwich {
+	pub versor: &'static str,
+	pub element: &'static str,
+	pub part: Option<SandwichPart>,
+}
+
+//	The sandwich restricted to some of the versor's and the element's blades,
+//	the rest taken as zero, emitted as an inherent method of the versor instead
+pub struct SandwichPart {
+	pub method: &'static str,
+	pub versor: &'static [&'static str],
+	pub element: &'static [&'static str],
+}
+
+const HEADER: &str = "// Generated by build.rs from the algebra's signature. Do not edit.\n";
+
+fn sum(terms: &[Term], mut factor: impl FnMut(&Term) -> String) -> String {
+	if terms.is_empty() {
+		return "S::ZERO".into();
+	}
+	let mut s = String::new();
+	for (n, t) in terms.iter().enumerate() {
+		match (n, t.coeff) {
+			(0, 1) => {}
+			(0, _) => s.push('-'),
+			(_, 1) => s.push_str(" + "),
+			(_, _) => s.push_str(" - "),
+		}
+		s.push_str(&factor(t));
+	}
+	s
+}
+
+//		Full multivector
+//	Unary involutions and duals, and the four products as inherent methods
+//	(`Self::geometric`, `wedge`, `vee`, `dot`) so the operator impls can try an
+//	accelerated path first.
+pub fn multivector(alg: &Algebra, name: &str) -> String {
+	let n = alg.len();
+	let mut s = String::from(HEADER);
+
+	let unary = |s: &mut String, doc: &str, method: &str, map: &dyn Fn(usize) -> (i8, usize)| {
+		writeln!(s, "\n{doc}\nimpl<S: Real> {name}<S> {{").unwrap();
+		writeln!(s, "    pub fn {method}(self: Self) -> Self {{").unwrap();
+		writeln!(s, "        let mut res = Self::zero();\n        let a = self;").unwrap();
+		for i in 0..n {
+			let (c, k) = map(i);
+			writeln!(s, "        res[{k}] = {}a[{i}];", if c < 0 { "-" } else { "" }).unwrap();
+		}
+		writeln!(s, "        res\n    }}\n}}").unwrap();
+	};
+
+	unary(&mut s, "// Reverse\n// Reverse the order of the basis blades.", "Reverse", &|i| (alg.reverse(i), i));
+	unary(&mut s, "// Dual\n// Poincare duality operator.", "Dual", &|i| alg.dual(i));
+	unary(&mut s, "// Conjugate\n// Clifford Conjugation", "Conjugate", &|i| (alg.conjugate(i), i));
+	unary(&mut s, "// Involute\n// Main involution", "Involute", &|i| (alg.involute(i), i));
+
+	writeln!(s, "\nimpl<S: Real> Not for {name}<S> {{\n    type Output = Self;\n").unwrap();
+	writeln!(s, "    fn not(self: Self) -> Self {{ self.Dual() }}\n}}").unwrap();
+
+	let all: Vec<usize> = (0..n).collect();
+	let body = |s: &mut String, product: Product| {
+		writeln!(s, "        let mut res = Self::zero();\n        let a = self;").unwrap();
+		for (k, terms) in alg.terms(product, &all, &all).iter().enumerate() {
+			if !terms.is_empty() {
+				writeln!(s, "        res[{k}] = {};", sum(terms, |t| format!("a[{}]*b[{}]", t.a, t.b))).unwrap();
+			}
+		}
+		writeln!(s, "        res").unwrap();
+	};
+
+	for (product, doc) in [
+		(Product::Geometric, "// Geometric product"),
+		(Product::Wedge, "// Wedge\n// The outer product. (MEET)"),
+		(Product::Vee, "// Vee\n// The regressive product. (JOIN)"),
+		(Product::Dot, "// Dot\n// The inner product."),
+	] {
+		let (op, _) = product.op();
+		let method = product.method();
+		writeln!(s, "\n{doc}\n// Reached through `{op}`.\nimpl<S: Real> {name}<S> {{").unwrap();
+		writeln!(s, "    #[inline(always)]\n    fn {method}(self: Self, b: Self) -> Self {{").unwrap();
+		body(&mut s, product);
+		writeln!(s, "    }}\n}}").unwrap();
+	}
+	s
+}
+
+//		Typed elements
+//	One `$define!(MV => Name { field: index, .. })` per element, then every listed
+//	product with only the terms that survive between the two operands' blades, and
+//	every sandwich expanded into one quadratic form of the versor per input blade.
+//	Panics (failing the build) if a result has weight outside its output type.
+pub fn elements(
+	alg: &Algebra, define: &str, mv: &str,
+	elements: &[Element], products: &[TypedProduct], sandwiches: &[TypedSandwich],
+) -> String {
+	let mut s = String::from(HEADER);
+
+	let blades = |e: &Element| -> Vec<usize> {
+		e.blades.iter()
+			.map(|&b| alg.blades.iter().position(|x| x.name == b).unwrap_or_else(|| panic!("{}: no blade `{}`", e.name, b)))
+			.collect()
+	};
+	let find = |name: &str| elements.iter().find(|e| e.name == name).unwrap_or_else(|| panic!("no element `{}`", name));
+
+	writeln!(s, "\n//	Define types").unwrap();
+	for e in elements {
+		let fields: Vec<String> = blades(e).iter().map(|&i| format!("{}: {}", alg.blades[i].field(), i)).collect();
+		writeln!(s, "{define}!({mv} => {} {{ {} }});", e.name, fields.join(", ")).unwrap();
+	}
+
+	let mut section = None;
+	for p in products {
+		if section != Some(p.product) {
+			section = Some(p.product);
+			let title = match p.product {
+				Product::Geometric => "Geometric product",
+				Product::Wedge => "Wedge (meet)",
+				Product::Vee => "Vee (join)",
+				Product::Dot => "Dot (inner product)",
+			};
+			writeln!(s, "\n//	{title}").unwrap();
+		}
+
+		let (lhs, rhs) = (find(p.lhs), find(p.rhs));
+		let terms = alg.terms(p.product, &blades(lhs), &blades(rhs));
+		let out: Vec<usize> = if p.out == "S" { vec![alg.index(0)] } else { blades(find(p.out)) };
+		for (k, t) in terms.iter().enumerate() {
+			if !t.is_empty() && !out.contains(&k) {
+				panic!("{} {} {} has {} weight, which {} does not store", p.lhs, p.product.op().0, p.rhs, alg.blades[k].name, p.out);
+			}
+		}
+
+		let (op, method) = p.product.op();
+		let (l, r, o) = (format!("{}<S>", p.lhs), format!("{}<S>", p.rhs), if p.out == "S" { "S".into() } else { format!("{}<S>", p.out) });
+		let term = |t: &Term| format!("a.{}*b.{}", alg.blades[t.a].field(), alg.blades[t.b].field());
+		writeln!(s, "impl<S: Real> {op}<{r}> for {l} {{\n\ttype Output = {o};\n").unwrap();
+		writeln!(s, "\t#[inline] fn {method}(self, b: {r}) -> {o} {{\n\t\tlet a = self;").unwrap();
+		if p.out == "S" {
+			writeln!(s, "\t\t{}", sum(&terms[out[0]], term)).unwrap();
+		} else {
+			writeln!(s, "\t\t{} {{", p.out).unwrap();
+			for &k in &out {
+				writeln!(s, "\t\t\t{}: {},", alg.blades[k].field(), sum(&terms[k], term)).unwrap();
+			}
+			writeln!(s, "\t\t}}").unwrap();
+		}
+		writeln!(s, "\t}}\n}}\n").unwrap();
+	}
+
+	if !sandwiches.is_empty() {
+		writeln!(s, "//	Sandwich product").unwrap();
+	}
+	for w in sandwiches {
+		let (mut v, out) = (blades(find(w.versor)), blades(find(w.element)));
+		let mut x = out.clone();
+		if let Some(part) = &w.part {
+			let subset = |of: &'static str, all: &[usize], some: &'static [&'static str]| -> Vec<usize> {
+				let some = blades(&Element { name: of, blades: some });
+				match some.iter().find(|b| !all.contains(b)) {
+					Some(&b) => panic!("{}: {} does not store `{}`", part.method, of, alg.blades[b].name),
+					None => all.iter().copied().filter(|b| some.contains(b)).collect(),
+				}
+			};
+			(v, x) = (subset(w.versor, &v, part.versor), subset(w.element, &x, part.element));
+		}
+
+		//	Coefficient of m_i m_k x_j in blade o, pairs (i, k) folded onto i <= k
+		let mut quad = vec![vec![Vec::<(i8, usize, usize)>::new(); alg.len()]; alg.len()];
+		for &i in &v {
+			for &j in &x {
+				for &k in &v {
+					let (c1, t) = alg.geometric(i, j);
+					let (c2, o) = alg.geometric(t, k);
+					let c = c1 * c2 * alg.reverse(k);
+					if c == 0 { continue; }
+					let (p, q) = if v.iter().position(|&b| b == i) <= v.iter().position(|&b| b == k) { (i, k) } else { (k, i) };
+					let terms = &mut quad[o][j];
+					match terms.iter_mut().find(|t| t.1 == p && t.2 == q) {
+						Some(t) => t.0 += c,
+						None => terms.push((c, p, q)),
+					}
+				}
+			}
+		}
+		for (o, row) in quad.iter().enumerate() {
+			if row.iter().flatten().any(|t| t.0 != 0) && !out.contains(&o) {
+				panic!("{} x ~{} has {} weight, which {} does not store", w.versor, w.versor, alg.blades[o].name, w.element);
+			}
+		}
+
+		let field = |i: usize| alg.blades[i].field();
+		let (name, xt) = (w.versor, format!("{}<S>", w.element));
+		match &w.part {
+			None => writeln!(s, "impl<S: Real> Sandwich<{xt}> for {name}<S> {{\n\t#[inline] fn apply(&self, x: {xt}) -> {xt} {{").unwrap(),
+			Some(part) => writeln!(s, "impl<S: Real> {name}<S> {{\n\t#[inline] pub fn {}(&self, x: {xt}) -> {xt} {{", part.method).unwrap(),
+		}
+		writeln!(s, "\t\tlet m = self;\n\t\t{} {{", w.element).unwrap();
+		for &o in &out {
+			//	Unit and doubled coefficients; the doubled group has its sign factored out
+			let mut summands: Vec<(bool, String)> = Vec::new();
+			for &j in &x {
+				let pairs = |mag: i8| -> Vec<Term> {
+					quad[o][j].iter().filter(|t| t.0.abs() == mag).map(|t| Term { coeff: t.0.signum(), a: t.1, b: t.2 }).collect()
+				};
+				if let Some(t) = quad[o][j].iter().find(|t| t.0.abs() > 2) {
+					panic!("{} x ~{}: unexpected coefficient {}", w.versor, w.versor, t.0);
+				}
+				let pair = |t: &Term| format!("m.{}*m.{}", field(t.a), field(t.b));
+				let ones = pairs(1);
+				if !ones.is_empty() {
+					summands.push((false, format!("({})*x.{}", sum(&ones, pair), field(j))));
+				}
+				let mut twos = pairs(2);
+				if !twos.is_empty() {
+					let negative = twos[0].coeff < 0;
+					if negative { twos.iter_mut().for_each(|t| t.coeff = -t.coeff); }
+					summands.push((negative, format!("S::TWO*({})*x.{}", sum(&twos, pair), field(j))));
+				}
+			}
+
+			let mut expr = String::new();
+			for (n, (negative, e)) in summands.iter().enumerate() {
+				match (n, negative) {
+					(0, false) => {}
+					(0, true) => expr.push('-'),
+					(_, false) => expr.push_str(" + "),
+					(_, true) => expr.push_str(" - "),
+				}
+				expr.push_str(e);
+			}
+			if expr.is_empty() { expr.push_str("S::ZERO"); }
+			writeln!(s, "\t\t\t{}: {},", field(o), expr).unwrap();
+		}
+		writeln!(s, "\t\t}}\n\t}}\n}}\n").unwrap();
+	}
+	s
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use super::super::algebra::tests::{pga3d, cga};
+
+	const PGA3D_ELEMENTS: &[Element] = &[
+		Element { name: "Plane", blades: &["e0", "e1", "e2", "e3"] },
+		Element { name: "Point", blades: &["e021", "e013", "e032", "e123"] },
+		Element { name: "Translator", blades: &["1", "e01", "e02", "e03"] },
+	];
+
+	fn line(code: &str, start: &str) -> String {
+		code.lines().map(str::trim).find(|l| l.starts_with(start)).unwrap_or_else(|| panic!("no `{}` in\n{}", start, code)).into()
+	}
+
+	//		Tests
+	#[test] fn test_multivector() {
+		let code = multivector(&pga3d(), "PGA3D");
+		assert!(code.starts_with(HEADER));
+		assert!(code.contains("// Reached through `BitAnd`.\nimpl<S: Real> PGA3D<S> {\n    #[inline(always)]\n    fn vee(self: Self, b: Self) -> Self {"));
+		let dual = &code[code.find("fn Dual").unwrap()..];
+		assert_eq!(line(dual, "res[15] = "), "res[15] = a[0];");
+
+		//	CGA dualises through I⁻¹ = -I
+		let code = multivector(&cga(), "CGA");
+		let dual = &code[code.find("fn Dual").unwrap()..];
+		assert_eq!(line(dual, "res[31] = "), "res[31] = -a[0];");
+		assert_eq!(line(dual, "res[0] = "), "res[0] = a[31];");
+	}
+
+	#[test] fn test_products() {
+		let products = &[
+			TypedProduct { product: Product::Dot, lhs: "Point", rhs: "Point", out: "S" },
+			TypedProduct { product: Product::Vee, lhs: "Point", rhs: "Plane", out: "S" },
+		];
+		let code = elements(&pga3d(), "define", "PGA3D", PGA3D_ELEMENTS, products, &[]);
+		assert!(code.contains("define!(PGA3D => Point { e021: 11, e013: 12, e032: 13, e123: 14 });"));
+		assert!(code.contains("impl<S: Real> BitOr<Point<S>> for Point<S> {\n\ttype Output = S;"));
+		assert!(code.contains("\t\t-a.e123*b.e123\n"));
+		assert!(code.contains("\t\ta.e021*b.e3 + a.e013*b.e2 + a.e032*b.e1 + a.e123*b.e0\n"));
+		assert!(!code.contains("Sandwich"));
+	}
+
+	#[test] fn test_sandwiches() {
+		let sandwiches = &[
+			TypedSandwich { versor: "Translator", element: "Point", part: None },
+			TypedSandwich { versor: "Translator", element: "Plane", part: None },
+		];
+		let code = elements(&pga3d(), "define", "PGA3D", PGA3D_ELEMENTS, &[], sandwiches);
+		assert!(code.contains("impl<S: Real> Sandwich<Point<S>> for Translator<S> {"));
+		assert_eq!(line(&code, "e021: "), "e021: (m.s*m.s)*x.e021 - S::TWO*(m.s*m.e03)*x.e123,");
+		assert_eq!(line(&code, "e123: "), "e123: (m.s*m.s)*x.e123,");
+		assert_eq!(line(&code, "e0: "), "e0: (m.s*m.s)*x.e0 + S::TWO*(m.s*m.e01)*x.e1 + S::TWO*(m.s*m.e02)*x.e2 + S::TWO*(m.s*m.e03)*x.e3,");
+	}
+
+	#[test] fn test_sandwich_part() {
+		//	A translator moves points by their weight; without it, not at all
+		let part = SandwichPart { method: "apply_direction", versor: &["1", "e01", "e02", "e03"], element: &["e021", "e013", "e032"] };
+		let sandwiches = &[TypedSandwich { versor: "Translator", element: "Point", part: Some(part) }];
+		let code = elements(&pga3d(), "define", "PGA3D", PGA3D_ELEMENTS, &[], sandwiches);
+		assert!(code.contains("impl<S: Real> Translator<S> {\n\t#[inline] pub fn apply_direction(&self, x: Point<S>) -> Point<S> {"));
+		assert!(!code.contains("Sandwich<"));
+		assert_eq!(line(&code, "e021: "), "e021: (m.s*m.s)*x.e021,");
+		assert_eq!(line(&code, "e123: "), "e123: S::ZERO,");
+	}
+
+	#[test] #[should_panic(expected = "apply_direction: Translator does not store `e12`")]
+	fn test_sandwich_part_out_of_type() {
+		let part = SandwichPart { method: "apply_direction", versor: &["1", "e12"], element: &["e021"] };
+		elements(&pga3d(), "define", "PGA3D", PGA3D_ELEMENTS, &[], &[TypedSandwich { versor: "Translator", element: "Point", part: Some(part) }]);
+	}
+
+	#[test] fn test_cga_elements() {
+		//	A rotor in the e12 plane turns e1 and e2, and leaves e0, e3 and e4 alone
+		let cga_elements = &[
+			Element { name: "Vector", blades: &["e0", "e1", "e2", "e3", "e4"] },
+			Element { name: "Bivector", blades: &["e01", "e02", "e03", "e04", "e12", "e13", "e14", "e23", "e24", "e34"] },
+			Element { name: "Rotor", blades: &["1", "e12"] },
+		];
+		let products = &[TypedProduct { product: Product::Wedge, lhs: "Vector", rhs: "Vector", out: "Bivector" }];
+		let sandwiches = &[TypedSandwich { versor: "Rotor", element: "Vector", part: None }];
+		let code = elements(&cga(), "define", "CGA", cga_elements, products, sandwiches);
+
+		assert!(code.contains("\t\t\te14: a.e1*b.e4 - a.e4*b.e1,\n"));
+		assert_eq!(line(&code, "e1: "), "e1: (m.s*m.s - m.e12*m.e12)*x.e1 + S::TWO*(m.s*m.e12)*x.e2,");
+		assert_eq!(line(&code, "e4: "), "e4: (m.s*m.s + m.e12*m.e12)*x.e4,");
+	}
+
+	#[test] #[should_panic(expected = "has e0 weight, which Plane does not store")]
+	fn test_sandwich_out_of_type() {
+		let types = &[
+			Element { name: "Plane", blades: &["e1", "e2", "e3"] },
+			Element { name: "Translator", blades: &["1", "e01", "e02", "e03"] },
+		];
+		elements(&pga3d(), "define", "PGA3D", types, &[], &[TypedSandwich { versor: "Translator", element: "Plane", part: None }]);
+	}
+
+	#[test] #[should_panic(expected = "Plane Mul Plane has 1 weight, which Plane does not store")]
+	fn test_product_out_of_type() {
+		let products = &[TypedProduct { product: Product::Geometric, lhs: "Plane", rhs: "Plane", out: "Plane" }];
+		elements(&pga3d(), "define", "PGA3D", PGA3D_ELEMENTS, products, &[]);
+	}
+}
@@ -1 +0,0 @@
-mod frame;
\ No newline at end of file
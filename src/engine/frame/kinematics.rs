@@ -0,0 +1,206 @@
+//  	Imports
+use bevy::prelude::*;
+use bevy::math::DVec3;
+
+use az::Cast;
+
+use crate::engine::math::{TypeVec3, FixQuat};
+
+//		Screw theoretic primitives
+//	Written once over the vector type: `FixVec3` for the world frame, `DVec3`
+//	for frames local to a parent (see `pose`). Rotations are `FixQuat` in both,
+//	so composing them stays deterministic; angular velocities are `DVec3` in
+//	both, as they never need interplanetary range.
+
+//	Pose - position and orientation of a frame, in its parent's axes
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, Component)]
+#[reflect(Component, Clone, Debug, Default, PartialEq)]
+pub struct Pose<V: TypeVec3> {
+	pub pos: V,
+	pub rot: FixQuat,
+}
+
+//	Twist - linear velocity of a frame's origin and its angular velocity, in
+//	the same axes as the matching pose
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect, Component)]
+#[reflect(Component, Clone, Debug, Default, PartialEq)]
+pub struct Twist<V: TypeVec3> {
+	pub lin: V,
+	pub ang: DVec3,
+}
+
+//	Component-wise cast between vector types
+#[inline] fn cast<A: TypeVec3, B: TypeVec3>(a: A) -> B where A::Scalar: Cast<B::Scalar> { B::new(a.x(), a.y(), a.z()) }
+
+//		Implementations
+impl<V: TypeVec3> Pose<V> {
+	//	Constructors
+	#[inline] pub fn new(pos: V, rot: FixQuat) -> Self { Self { pos, rot } }
+	#[inline] pub fn from_pos(pos: V) -> Self { Self { pos, rot: FixQuat::IDENTITY } }
+
+	//	Point and direction given in this frame, in the parent's
+	#[inline] pub fn transform_point(&self, p: V) -> V { self.pos + p.rotate(self.rot) }
+	#[inline] pub fn transform_vector(&self, v: V) -> V { v.rotate(self.rot) }
+
+	//	`local`, given relative to this frame, relative to this frame's parent.
+	//	Its position is cast into `V` first, so with `V = FixVec3` the result is
+	//	deterministic.
+	pub fn compose<L: TypeVec3>(&self, local: &Pose<L>) -> Self where L::Scalar: Cast<V::Scalar> {
+		Self { pos: self.transform_point(cast(local.pos)), rot: (self.rot * local.rot).normalize() }
+	}
+
+	//	The parent relative to this frame
+	pub fn inverse(&self) -> Self {
+		let rot = self.rot.conjugate();
+		Self { pos: -self.pos.rotate(rot), rot }
+	}
+
+	//	This pose relative to `frame`, both given in the same parent: the inverse
+	//	of `frame.compose`. The offset is taken in `V`, where it is exact, and
+	//	rotated in `L`.
+	pub fn relative_to<L: TypeVec3>(&self, frame: &Self) -> Pose<L> where V::Scalar: Cast<L::Scalar> {
+		let inv = frame.rot.conjugate();
+		Pose { pos: cast::<V, L>(self.pos - frame.pos).rotate(inv), rot: (inv * self.rot).normalize() }
+	}
+
+	//	Adjoint: a twist given in this frame (about its origin, in its axes) as
+	//	the same motion in the parent, about the parent's origin:
+	//		ω' = R ω
+	//		v' = R v + p × R ω
+	pub fn adjoint(&self, twist: &Twist<V>) -> Twist<V> where f64: Cast<V::Scalar> {
+		let ang = self.rot.to_dquat() * twist.ang;
+		Twist { lin: self.transform_vector(twist.lin) + cast(self.pos.to_f64().cross(ang)), ang }
+	}
+}
+
+impl<V: TypeVec3> Twist<V> {
+	#[inline] pub fn new(lin: V, ang: DVec3) -> Self { Self { lin, ang } }
+
+	//	Twist of a child at `pose`, moving at `local` relative to its parent at
+	//	`parent`, which moves at this twist. With r the child's offset from the
+	//	parent and R the parent's rotation:
+	//		v = v_p + ω_p × r + R v_rel
+	//		ω = ω_p + R ω_rel
+	//	ω_p × r is the transport term that spinning parents drag their children
+	//	along with.
+	pub fn compose<L: TypeVec3>(&self, parent: &Pose<V>, pose: &Pose<V>, local: &Twist<L>) -> Self
+		where L::Scalar: Cast<V::Scalar>, f64: Cast<V::Scalar>
+	{
+		let drag = self.ang.cross((pose.pos - parent.pos).to_f64());
+		Self {
+			lin: self.lin + cast(drag) + parent.transform_vector(cast(local.lin)),
+			ang: self.ang + parent.rot.to_dquat() * local.ang,
+		}
+	}
+
+	//	Inverse of `compose`: this twist of a child at `pose` relative to its
+	//	parent at `parent`, moving at `parent_twist`
+	pub fn relative_to<L: TypeVec3>(&self, parent_twist: &Self, parent: &Pose<V>, pose: &Pose<V>) -> Twist<L>
+		where V::Scalar: Cast<L::Scalar>, f64: Cast<L::Scalar>
+	{
+		let drag = parent_twist.ang.cross((pose.pos - parent.pos).to_f64());
+		let inv = parent.rot.conjugate();
+		Twist {
+			lin: (cast::<V, L>(self.lin - parent_twist.lin) - cast(drag)).rotate(inv),
+			ang: inv.to_dquat() * (self.ang - parent_twist.ang),
+		}
+	}
+
+	//	Coriolis acceleration 2ω_p × R v_rel of a child moving at `local` inside a
+	//	parent rotating at this twist. It has no counterpart at velocity level
+	//	(`compose` is exact there) but shows up as soon as a child's world
+	//	velocity is differentiated, e.g. for apparent-force readouts.
+	pub fn coriolis<L: TypeVec3>(&self, parent: &Pose<V>, local: &Twist<L>) -> DVec3 {
+		2. * self.ang.cross(parent.rot.to_dquat() * local.lin.to_f64())
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy::math::DQuat;
+	use crate::engine::math::{FixVec3, FixOrigin};
+
+	fn quat(axis: DVec3, angle: f64) -> FixQuat { FixQuat::from_dquat(DQuat::from_axis_angle(axis.normalize(), angle)) }
+
+	fn pose_f64() -> Pose<DVec3> { Pose::new(DVec3::new(3., -1., 2.), quat(DVec3::new(1., 2., -1.), 0.8)) }
+	fn pose_fix() -> Pose<FixVec3> { Pose::new(FixVec3::new(1.2e11, -4e10, 7e9), quat(DVec3::new(-2., 1., 3.), 2.1)) }
+
+	#[test] fn test_transform() {
+		let p = Pose::new(DVec3::new(10., 0., 0.), quat(DVec3::Z, std::f64::consts::FRAC_PI_2));
+		assert!(p.transform_point(DVec3::X).abs_diff_eq(DVec3::new(10., 1., 0.), 1e-15));
+		assert!(p.transform_vector(DVec3::X).abs_diff_eq(DVec3::Y, 1e-15));
+		assert!(p.inverse().transform_point(DVec3::new(10., 1., 0.)).abs_diff_eq(DVec3::X, 1e-14));
+	}
+
+	#[test] fn test_compose_inverse() {
+		let (a, b) = (pose_f64(), Pose::new(DVec3::new(-5., 0.5, 1.), quat(DVec3::Y, -1.3)));
+		let q = DVec3::new(0.3, 0.7, -2.);
+		assert!(a.compose(&b).transform_point(q).abs_diff_eq(a.transform_point(b.transform_point(q)), 1e-14));
+
+		let id = a.compose(&a.inverse());
+		assert!(id.pos.abs_diff_eq(DVec3::ZERO, 1e-15) && id.rot.to_dquat().abs_diff_eq(DQuat::IDENTITY, 1e-15));
+
+		//	Fixed point: the inverse lands back within rounding of the origin
+		let f = pose_fix();
+		let id = f.compose(&f.inverse());
+		assert!(id.pos.length() <= FixOrigin::DELTA * 4);
+	}
+
+	#[test] fn test_relative_to() {
+		//	relative_to undoes compose, across vector types
+		let parent = pose_fix();
+		let local = Pose::new(DVec3::new(3.8e8, -2.5e7, 1e6), quat(DVec3::new(0., 1., 1.), 0.4));
+		let world = parent.compose(&local);
+		let back: Pose<DVec3> = world.relative_to(&parent);
+		assert!(back.pos.abs_diff_eq(local.pos, 1e-4));
+		assert!(back.rot.to_dquat().abs_diff_eq(local.rot.to_dquat(), 1e-15));
+
+		let (a, b) = (pose_f64(), Pose::new(DVec3::new(1., 1., 1.), quat(DVec3::X, 2.)));
+		let rel: Pose<DVec3> = a.relative_to(&b);
+		assert!(b.compose(&rel).pos.abs_diff_eq(a.pos, 1e-14));
+	}
+
+	#[test] fn test_twist_relative_to() {
+		let parent = pose_fix();
+		let parent_twist = Twist::new(FixVec3::new(2.9e4, -1e3, 0.), DVec3::new(0., 0., 7.3e-5));
+		let local = Pose::new(DVec3::new(6.7e6, 0., 1e5), FixQuat::IDENTITY);
+		let local_twist = Twist::new(DVec3::new(10., 7.7e3, -3.), DVec3::new(0.01, 0., 0.));
+
+		let pose = parent.compose(&local);
+		let twist = parent_twist.compose(&parent, &pose, &local_twist);
+		let back: Twist<DVec3> = twist.relative_to(&parent_twist, &parent, &pose);
+		assert!(back.lin.abs_diff_eq(local_twist.lin, 1e-4));
+		assert!(back.ang.abs_diff_eq(local_twist.ang, 1e-15));
+	}
+
+	#[test] fn test_adjoint() {
+		//	Body twist: origin velocity and angular velocity in the body's axes
+		let pose = pose_f64();
+		let body = Twist::new(DVec3::new(0.5, -2., 1.), DVec3::new(0.3, 0.1, -0.7));
+		let spatial = pose.adjoint(&body);
+
+		//	Any point fixed in the body moves at v' + ω' × x in the parent
+		let q = DVec3::new(1., -3., 0.5);
+		let at = |t: f64| {
+			let rot = pose.rot.to_dquat() * DQuat::from_scaled_axis(body.ang * t);
+			pose.pos + pose.rot.to_dquat() * body.lin * t + rot * q
+		};
+		let dt = 1e-5;
+		let numeric = (at(dt) - at(-dt)) / (2. * dt);
+		let x = pose.transform_point(q);
+		assert!((spatial.lin + spatial.ang.cross(x)).abs_diff_eq(numeric, 1e-8));
+
+		//	And the inverse pose transports it back
+		let back = pose.inverse().adjoint(&spatial);
+		assert!(back.lin.abs_diff_eq(body.lin, 1e-14) && back.ang.abs_diff_eq(body.ang, 1e-15));
+
+		//	Fixed point, with the p × Rω term taken in f64
+		let pose = pose_fix();
+		let body = Twist::new(FixVec3::new(100, 0, 0), DVec3::new(0., 0., 1e-6));
+		let spatial = pose.adjoint(&body);
+		let expect = pose.rot.to_dquat() * DVec3::new(100., 0., 0.)
+			+ pose.pos.to_f64().cross(pose.rot.to_dquat() * body.ang);
+		assert!(spatial.lin.to_f64().abs_diff_eq(expect, 1e-4));
+	}
+}
@@ -0,0 +1,12 @@
+pub mod kinematics;
+pub mod pose;
+pub mod propagate;
+pub mod reparent;
+pub mod render;
+
+//  Re-exports
+pub use kinematics::{Pose, Twist};
+pub use pose::{WorldPose, WorldTwist, ParentFrame, LocalPose, LocalTwist};
+pub use propagate::{FrameFault, FramePropagationSystems, FramePropagationPlugin};
+pub use reparent::{ReparentFrame, ReparentResidual};
+pub use render::{FloatingOrigin, RenderOrigin, FloatingOriginSystems, FloatingOriginPlugin};
@@ -0,0 +1,24 @@
+//  	Imports
+use bevy::prelude::*;
+use bevy::math::DVec3;
+
+use crate::engine::math::FixVec3;
+use super::kinematics::{Pose, Twist};
+
+//		Definitions
+//	World frame
+//	Absolute placement, exact at interplanetary scale; the twist is the
+//	velocity of the origin and the angular velocity, both in world axes
+pub type WorldPose = Pose<FixVec3>;
+pub type WorldTwist = Twist<FixVec3>;
+
+//	Local frame
+//	Relative to the `ParentFrame`, in its axes
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect, Component)]
+#[reflect(Component)]
+pub struct ParentFrame(pub Entity);
+
+//	The rotation is a `FixQuat` like the world one, so it reflects as an opaque
+//	value, where the offset and the local twist reflect field by field
+pub type LocalPose = Pose<DVec3>;
+pub type LocalTwist = Twist<DVec3>;
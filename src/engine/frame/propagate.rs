@@ -0,0 +1,345 @@
+//  	Imports
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+
+use super::pose::{WorldPose, WorldTwist, ParentFrame, LocalPose, LocalTwist};
+use super::render::FloatingOriginSystems;
+use super::reparent::ReparentResidual;
+
+//		Frame propagation
+//	Entities with `ParentFrame` and `LocalPose` get their `WorldPose` composed
+//	onto their parent's, parents first, however deep the chain (ship docked at
+//	a station orbiting a moon orbiting a planet). Roots are whatever a chain
+//	ends on: an entity with a `WorldPose` and no `ParentFrame`.
+//	Below a root with a `WorldTwist`, children get one too, from their
+//	`LocalTwist` (none is taken as rigidly attached); below one without, any
+//	twist they had is removed. Entities moved by `reparent_frame` also carry
+//	their `ReparentResidual` along.
+//	A child that cannot be placed keeps its last `WorldPose`, loses its
+//	`WorldTwist`, and is tagged with a `FrameFault` until its chain is repaired.
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Component)]
+pub enum FrameFault {
+	//	The chain ends on an entity that is gone, has no `WorldPose`, or leads
+	//	into a cycle
+	Orphan,
+	//	The entity's own `ParentFrame` chain loops back to it
+	Cycle,
+}
+
+//	Runs in `PostUpdate`, ahead of the floating origin
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, SystemSet)]
+pub struct FramePropagationSystems;
+
+pub struct FramePropagationPlugin;
+
+impl Plugin for FramePropagationPlugin {
+	fn build(&self, app: &mut App) {
+		app.register_type::<ParentFrame>()
+			.register_type::<WorldPose>().register_type::<WorldTwist>()
+			.register_type::<LocalPose>().register_type::<LocalTwist>()
+			.configure_sets(PostUpdate, FramePropagationSystems.before(FloatingOriginSystems))
+			.add_systems(PostUpdate, propagate_frames.in_set(FramePropagationSystems));
+	}
+}
+
+//		Systems
+type Child<'a> = (
+	Entity, &'a ParentFrame, &'a LocalPose, Option<&'a LocalTwist>,
+	Option<&'a ReparentResidual>, Option<&'a FrameFault>,
+);
+//	What a parent hands down: its pose, and its twist if it has one
+pub(crate) type Placement = (WorldPose, Option<WorldTwist>);
+
+//	Placement of a child at `local` below a parent placed at `parent`
+pub(crate) fn place(parent: &Placement, local: &LocalPose, local_twist: Option<&LocalTwist>) -> Placement {
+	let (parent, twist) = parent;
+	let pose = parent.compose(local);
+	let twist = twist.map(|twist| twist.compose(parent, &pose, local_twist.unwrap_or(&LocalTwist::default())));
+	(pose, twist)
+}
+
+fn propagate_frames(
+	mut commands: Commands,
+	children: Query<Child>,
+	mut poses: Query<&mut WorldPose>,
+	mut twists: Query<&mut WorldTwist>,
+) {
+	let mut by_parent: HashMap<Entity, Vec<Entity>> = HashMap::new();
+	for (child, parent, ..) in &children {
+		by_parent.entry(parent.0).or_default().push(child);
+	}
+
+	//	Depth-first from the roots, carrying each parent's pose and twist down;
+	//	None below a root without a `WorldPose`
+	let mut stack: Vec<(Entity, Option<Placement>)> = by_parent.keys()
+		.filter(|&&parent| !children.contains(parent))
+		.map(|&root| (root, poses.get(root).ok().map(|pose| (*pose, twists.get(root).ok().copied()))))
+		.collect();
+	let mut reached = HashSet::new();
+	while let Some((parent, world)) = stack.pop() {
+		for &child in by_parent.get(&parent).into_iter().flatten() {
+			let (_, _, local, local_twist, residual, fault) = children.get(child).expect("grouped from `children`");
+			let mut world = world.map(|parent| place(&parent, local, local_twist));
+			match (residual, &mut world) {
+				//	Solved under another parent
+				(Some(residual), _) if residual.parent() != parent => { commands.entity(child).remove::<ReparentResidual>(); },
+				(Some(residual), Some(placement)) => residual.apply(placement),
+				_ => (),
+			}
+			match world {
+				Some((pose, twist)) => {
+					match poses.get_mut(child) {
+						Ok(mut current) => { current.set_if_neq(pose); },
+						Err(_) => { commands.entity(child).insert(pose); },
+					}
+					match (twist, twists.get_mut(child)) {
+						(Some(twist), Ok(mut current)) => { current.set_if_neq(twist); },
+						(Some(twist), Err(_)) => { commands.entity(child).insert(twist); },
+						(None, Ok(_)) => { commands.entity(child).remove::<WorldTwist>(); },
+						(None, Err(_)) => (),
+					}
+					if fault.is_some() {
+						commands.entity(child).remove::<FrameFault>();
+					}
+				},
+				None => set_fault(&mut commands, child, fault, FrameFault::Orphan),
+			}
+			reached.insert(child);
+			stack.push((child, world));
+		}
+	}
+
+	//	Whatever is left hangs off a cycle, as every chain that ends leads to a
+	//	root. Follow parents until the walk meets itself (the entities from there
+	//	on form the cycle) or an entity already classified.
+	let mut classified: HashMap<Entity, FrameFault> = HashMap::new();
+	for (start, ..) in &children {
+		if reached.contains(&start) || classified.contains_key(&start) {
+			continue;
+		}
+		let (mut path, mut on_path) = (vec![], HashMap::new());
+		let mut e = start;
+		while !classified.contains_key(&e) && !on_path.contains_key(&e) {
+			on_path.insert(e, path.len());
+			path.push(e);
+			e = children.get(e).expect("unreached entities are children").1.0;
+		}
+		let cycle_from = on_path.get(&e).copied().unwrap_or(path.len());
+		for (i, e) in path.into_iter().enumerate() {
+			classified.insert(e, if i >= cycle_from { FrameFault::Cycle } else { FrameFault::Orphan });
+		}
+	}
+	for (e, fault) in classified {
+		let (.., current) = children.get(e).expect("classified entities are children");
+		set_fault(&mut commands, e, current, fault);
+	}
+}
+
+//	A faulted child has no root to move with, so its twist goes with the fault
+fn set_fault(commands: &mut Commands, e: Entity, current: Option<&FrameFault>, fault: FrameFault) {
+	if current != Some(&fault) {
+		commands.entity(e).insert(fault).remove::<WorldTwist>();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy::math::{DQuat, DVec3};
+	use std::any::TypeId;
+	use std::f64::consts::FRAC_PI_2;
+	use crate::engine::math::{FixVec3, FixQuat};
+
+	fn app() -> App {
+		let mut app = App::new();
+		app.add_plugins(FramePropagationPlugin);
+		app
+	}
+
+	fn local(x: f64, y: f64, z: f64) -> LocalPose { LocalPose::from_pos(DVec3::new(x, y, z)) }
+
+	fn child(app: &mut App, parent: Entity, local: LocalPose) -> Entity {
+		app.world_mut().spawn((ParentFrame(parent), local)).id()
+	}
+
+	fn world(app: &App, e: Entity) -> WorldPose { *app.world().get::<WorldPose>(e).unwrap() }
+	fn twist(app: &App, e: Entity) -> WorldTwist { *app.world().get::<WorldTwist>(e).unwrap() }
+	fn near(v: FixVec3, expect: DVec3) -> bool { v.to::<DVec3>().abs_diff_eq(expect, 1e-4) }
+	fn fault(app: &App, e: Entity) -> Option<FrameFault> { app.world().get::<FrameFault>(e).copied() }
+
+	#[test] fn test_chain() {
+		let mut app = app();
+		//	Planet turned a quarter about Z, moon along its X, ship docked off the moon
+		let rot = FixQuat::from_dquat(DQuat::from_rotation_z(FRAC_PI_2));
+		let planet = app.world_mut().spawn(WorldPose::new(FixVec3::new(1.5e11, 0., 0.), rot)).id();
+		//	Spawned leaf first: the walk does not depend on entity order
+		let ship = app.world_mut().spawn_empty().id();
+		let moon = child(&mut app, planet, LocalPose { pos: DVec3::new(4e8, 0., 0.), rot: FixQuat::from_dquat(DQuat::from_rotation_z(-FRAC_PI_2)) });
+		app.world_mut().entity_mut(ship).insert((ParentFrame(moon), local(0., 25., 0.)));
+		app.update();
+
+		assert_eq!(world(&app, moon).pos, FixVec3::new(1.5e11, 4e8, 0.));
+		assert_eq!(world(&app, ship).pos, FixVec3::new(1.5e11, 4e8 + 25., 0.));
+		assert!(world(&app, ship).rot.to_dquat().abs_diff_eq(DQuat::IDENTITY, 1e-12));
+
+		//	Moving the root moves everything below it on the next update
+		app.world_mut().get_mut::<WorldPose>(planet).unwrap().pos = FixVec3::ZERO;
+		app.update();
+		assert_eq!(world(&app, ship).pos, FixVec3::new(0., 4e8 + 25., 0.));
+		assert_eq!(fault(&app, ship), None);
+	}
+
+	#[test] fn test_orphans() {
+		let mut app = app();
+		let gone = app.world_mut().spawn(WorldPose::default()).id();
+		let station = child(&mut app, gone, local(1., 0., 0.));
+		let ship = child(&mut app, station, local(1., 0., 0.));
+		app.update();
+		assert_eq!(world(&app, ship).pos, FixVec3::new(2, 0, 0));
+
+		//	The last good pose is kept while the chain is broken
+		app.world_mut().despawn(gone);
+		app.update();
+		assert_eq!((fault(&app, station), fault(&app, ship)), (Some(FrameFault::Orphan), Some(FrameFault::Orphan)));
+		assert_eq!(world(&app, ship).pos, FixVec3::new(2, 0, 0));
+
+		//	Re-attached, the fault clears
+		let root = app.world_mut().spawn(WorldPose::from_pos(FixVec3::new(10, 0, 0))).id();
+		app.world_mut().get_mut::<ParentFrame>(station).unwrap().0 = root;
+		app.update();
+		assert_eq!((fault(&app, station), fault(&app, ship)), (None, None));
+		assert_eq!(world(&app, ship).pos, FixVec3::new(12, 0, 0));
+	}
+
+	#[test] fn test_cycles() {
+		let mut app = app();
+		let a = app.world_mut().spawn_empty().id();
+		let b = child(&mut app, a, local(1., 0., 0.));
+		app.world_mut().entity_mut(a).insert((ParentFrame(b), local(1., 0., 0.)));
+		let hanger = child(&mut app, b, local(1., 0., 0.));
+		let self_loop = app.world_mut().spawn_empty().id();
+		app.world_mut().entity_mut(self_loop).insert((ParentFrame(self_loop), local(0., 0., 0.)));
+		let root = app.world_mut().spawn(WorldPose::default()).id();
+		let fine = child(&mut app, root, local(1., 0., 0.));
+		app.update();
+
+		assert_eq!((fault(&app, a), fault(&app, b)), (Some(FrameFault::Cycle), Some(FrameFault::Cycle)));
+		assert_eq!(fault(&app, self_loop), Some(FrameFault::Cycle));
+		assert_eq!(fault(&app, hanger), Some(FrameFault::Orphan));
+		assert_eq!((fault(&app, fine), world(&app, fine).pos), (None, FixVec3::new(1, 0, 0)));
+		assert!(app.world().get::<WorldPose>(a).is_none());
+	}
+
+	#[test] fn test_twists() {
+		let mut app = app();
+		//	Station a quarter turn about Z, drifting along Y and spinning about Z
+		let rot = FixQuat::from_dquat(DQuat::from_rotation_z(FRAC_PI_2));
+		let station_pose = WorldPose::new(FixVec3::new(1000, 0, 0), rot);
+		let station_twist = WorldTwist { lin: FixVec3::new(0, 5, 0), ang: DVec3::new(0., 0., 0.1) };
+		let station = app.world_mut().spawn((station_pose, station_twist)).id();
+		//	Rigidly attached 100 m out along the station's X, i.e. world Y
+		let module = child(&mut app, station, local(100., 0., 0.));
+		//	Driving along the module's X, i.e. world Y, and spinning about it
+		let rover_twist = LocalTwist { lin: DVec3::new(1., 0., 0.), ang: DVec3::new(0.2, 0., 0.) };
+		let rover = child(&mut app, module, LocalPose::default());
+		app.world_mut().entity_mut(rover).insert(rover_twist);
+		//	No twist at the root, none below it
+		let still = app.world_mut().spawn(WorldPose::default()).id();
+		let moored = child(&mut app, still, local(1., 0., 0.));
+		app.update();
+
+		//	ω × r = 0.1 ẑ × 100 ŷ
+		assert!(near(twist(&app, module).lin, DVec3::new(-10., 5., 0.)));
+		assert_eq!(twist(&app, module).ang, station_twist.ang);
+		assert!(near(twist(&app, rover).lin, DVec3::new(-10., 6., 0.)));
+		assert!(twist(&app, rover).ang.abs_diff_eq(DVec3::new(0., 0.2, 0.1), 1e-12));
+		assert!(app.world().get::<WorldTwist>(moored).is_none());
+
+		//	2 ω × R v_rel = 2 · 0.1 ẑ × ŷ
+		let coriolis = twist(&app, module).coriolis(&world(&app, module), &rover_twist);
+		assert!(coriolis.abs_diff_eq(DVec3::new(-0.2, 0., 0.), 1e-12));
+	}
+
+	#[test] fn test_reflect() {
+		use bevy::reflect::ReflectRef;
+		let app = app();
+		let registry = app.world().resource::<AppTypeRegistry>().read();
+		for id in [TypeId::of::<ParentFrame>(), TypeId::of::<WorldPose>(), TypeId::of::<WorldTwist>(), TypeId::of::<LocalPose>(), TypeId::of::<LocalTwist>()] {
+			assert!(registry.get_type_data::<ReflectComponent>(id).is_some());
+		}
+		//	Fixed-point fields come along, serializable for scenes
+		#[cfg(feature = "serde")]
+		for id in [TypeId::of::<FixVec3>(), TypeId::of::<FixQuat>()] {
+			assert!(registry.get_type_data::<bevy::reflect::ReflectSerialize>(id).is_some());
+		}
+
+		//	Poses and twists reflect field by field, fixed-point fields as opaque values
+		let pose = LocalPose { pos: DVec3::new(1., 2., 3.), rot: FixQuat::from_dquat(DQuat::from_rotation_x(0.5)) };
+		let ReflectRef::Struct(fields) = pose.reflect_ref() else { panic!("poses reflect as structs") };
+		assert_eq!(fields.field("pos").and_then(|f| f.try_downcast_ref::<DVec3>()), Some(&pose.pos));
+		let rot = fields.field("rot").unwrap();
+		assert!(matches!(rot.reflect_ref(), ReflectRef::Opaque(_)));
+		assert_eq!(rot.reflect_clone().unwrap().take::<FixQuat>().unwrap(), pose.rot);
+		assert_eq!(pose.reflect_partial_eq(&pose), Some(true));
+
+		let twist = WorldTwist { lin: FixVec3::new(1, 2, 3), ang: DVec3::Z };
+		let mut copy = WorldTwist::default();
+		copy.apply(&twist);
+		assert_eq!(copy, twist);
+		let local_twist = LocalTwist::default();
+		let ReflectRef::Struct(fields) = local_twist.reflect_ref() else { panic!("twists reflect as structs") };
+		assert!(matches!(fields.field("lin").unwrap().reflect_ref(), ReflectRef::Struct(_)));
+	}
+
+	#[test] fn test_twist_removed() {
+		let mut app = app();
+		let station = app.world_mut().spawn((WorldPose::default(), WorldTwist { lin: FixVec3::new(0, 5, 0), ang: DVec3::Z })).id();
+		let module = child(&mut app, station, local(100., 0., 0.));
+		let rover = child(&mut app, module, local(1., 0., 0.));
+		app.update();
+		assert!(app.world().get::<WorldTwist>(rover).is_some());
+
+		//	The root stops carrying a twist: so do its children
+		app.world_mut().entity_mut(station).remove::<WorldTwist>();
+		app.update();
+		assert!(app.world().get::<WorldTwist>(module).is_none() && app.world().get::<WorldTwist>(rover).is_none());
+		assert_eq!(world(&app, rover).pos, FixVec3::new(101, 0, 0));
+
+		//	And an orphan has no root to move with
+		app.world_mut().entity_mut(station).insert(WorldTwist::default());
+		app.update();
+		assert!(app.world().get::<WorldTwist>(rover).is_some());
+		app.world_mut().despawn(station);
+		app.update();
+		assert_eq!(fault(&app, rover), Some(FrameFault::Orphan));
+		assert!(app.world().get::<WorldTwist>(module).is_none() && app.world().get::<WorldTwist>(rover).is_none());
+		assert_eq!(world(&app, rover).pos, FixVec3::new(101, 0, 0));
+	}
+
+	#[test] fn test_twist_matches_motion() {
+		//	The composed twist is the derivative of the composed pose
+		let (p0, v) = (DVec3::new(3e9, -2e9, 1e9), DVec3::new(120., -40., 7.));
+		let (r0, omega) = (DQuat::from_rotation_y(0.7), DVec3::new(0.3, -0.2, 0.5));
+		let local_twist = LocalTwist { lin: DVec3::new(-2., 3., 0.5), ang: DVec3::ZERO };
+
+		let at = |t: f64| {
+			let parent = WorldPose::new(
+				FixVec3::new(p0.x + v.x * t, p0.y + v.y * t, p0.z + v.z * t),
+				FixQuat::from_dquat(DQuat::from_scaled_axis(omega * t) * r0),
+			);
+			let local = LocalPose::from_pos(DVec3::new(50., 20., -10.) + local_twist.lin * t);
+			(parent, parent.compose(&local))
+		};
+
+		let (parent, pose) = at(0.);
+		let parent_twist = WorldTwist { lin: FixVec3::new(v.x, v.y, v.z), ang: omega };
+		let twist = parent_twist.compose(&parent, &pose, &local_twist);
+
+		let dt = 0.01;
+		let (ahead, behind) = (at(dt).1.pos.to::<DVec3>(), at(-dt).1.pos.to::<DVec3>());
+		let numeric = (ahead - behind) / (2. * dt);
+		assert!(twist.lin.to::<DVec3>().abs_diff_eq(numeric, 2e-3), "{:?} vs {:?}", twist.lin, numeric);
+	}
+}
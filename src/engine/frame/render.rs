@@ -0,0 +1,167 @@
+//  	Imports
+use bevy::prelude::*;
+use bevy::math::DVec3;
+
+use crate::engine::math::{FixVec3, FixOrigin};
+use super::kinematics::Pose;
+use super::pose::WorldPose;
+
+//		Floating origin
+//	Rendering runs in f32, which resolves ~1 m at 1e7 m and nothing useful at
+//	interplanetary range. Transforms are written relative to a render origin
+//	kept near the camera, so whatever is on screen sits close to zero. The
+//	origin only moves in whole steps, when the entity it follows drifts past
+//	`RenderOrigin::recentre`; every transform is rewritten in that same frame,
+//	so re-centring is invisible.
+//
+//	A Bevy child's `Transform` is relative to its parent. Children whose parent
+//	has a `WorldPose` get their pose relative to the parent's instead, which
+//	the origin drops out of; this assumes unscaled parents. Children of
+//	entities without one are left to whoever places that parent.
+
+//	Marks the entity the render origin follows, usually the active camera.
+//	With none (or several) the origin stays where it is.
+#[derive(Clone, Copy, Debug, Default, Component)]
+pub struct FloatingOrigin;
+
+#[derive(Clone, Copy, Debug, Resource)]
+pub struct RenderOrigin {
+	pub pos: FixVec3,
+	//	Re-centre distance: f32 keeps ~1 mm out to the default 10 km
+	pub recentre: FixOrigin,
+}
+
+impl Default for RenderOrigin {
+	fn default() -> Self { Self { pos: FixVec3::ZERO, recentre: FixOrigin::lit("10000") } }
+}
+
+//	Runs in `PostUpdate`, ahead of transform propagation; anything that moves
+//	`WorldPose` in `PostUpdate` goes before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, SystemSet)]
+pub struct FloatingOriginSystems;
+
+pub struct FloatingOriginPlugin;
+
+impl Plugin for FloatingOriginPlugin {
+	fn build(&self, app: &mut App) {
+		app.init_resource::<RenderOrigin>()
+			.configure_sets(PostUpdate, FloatingOriginSystems.before(TransformSystems::Propagate))
+			.add_systems(PostUpdate, (recentre_origin, write_transforms).chain().in_set(FloatingOriginSystems));
+	}
+}
+
+//		Systems
+fn recentre_origin(mut origin: ResMut<RenderOrigin>, followed: Query<&WorldPose, With<FloatingOrigin>>) {
+	let Ok(pose) = followed.single() else { return };
+	if pose.pos.distance(origin.pos) > origin.recentre {
+		origin.pos = pose.pos;
+	}
+}
+
+fn write_transforms(
+	origin: Res<RenderOrigin>,
+	mut roots: Query<(Ref<WorldPose>, &mut Transform), Without<ChildOf>>,
+	mut children: Query<(Ref<WorldPose>, &ChildOf, &mut Transform)>,
+	parents: Query<Ref<WorldPose>>,
+) {
+	for (pose, mut transform) in &mut roots {
+		if !(origin.is_changed() || pose.is_changed()) {
+			continue;
+		}
+		//	Exact in f64 within 2^37 m of the origin; the f32 cast is the only loss
+		transform.translation = (pose.pos - origin.pos).to::<DVec3>().as_vec3();
+		transform.rotation = pose.rot.to_dquat().as_quat();
+	}
+
+	for (pose, child_of, mut transform) in &mut children {
+		let Ok(parent) = parents.get(child_of.parent()) else { continue };
+		if !(pose.is_changed() || parent.is_changed()) {
+			continue;
+		}
+		let local: Pose<DVec3> = pose.relative_to(&parent);
+		transform.translation = local.pos.as_vec3();
+		transform.rotation = local.rot.to_dquat().as_quat();
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::engine::math::FixQuat;
+
+	fn app() -> App {
+		let mut app = App::new();
+		app.add_plugins(FloatingOriginPlugin);
+		app
+	}
+
+	fn spawn(app: &mut App, pos: FixVec3) -> Entity {
+		app.world_mut().spawn((WorldPose::from_pos(pos), Transform::default())).id()
+	}
+
+	fn translation(app: &App, e: Entity) -> Vec3 { app.world().get::<Transform>(e).unwrap().translation }
+
+	#[test] fn test_far_offsets() {
+		let mut app = app();
+		//	1 AU out, where f32 alone resolves ~16 km
+		let far = FixVec3::new(1.496e11, 0., 0.);
+		let cam = spawn(&mut app, far);
+		app.world_mut().entity_mut(cam).insert(FloatingOrigin);
+		let ship = spawn(&mut app, far + FixVec3::new(1.25, -0.5, 3.));
+		app.update();
+
+		assert_eq!(app.world().resource::<RenderOrigin>().pos, far);
+		assert_eq!(translation(&app, cam), Vec3::ZERO);
+		assert_eq!(translation(&app, ship), Vec3::new(1.25, -0.5, 3.));
+	}
+
+	#[test] fn test_recentre() {
+		let mut app = app();
+		let cam = spawn(&mut app, FixVec3::ZERO);
+		app.world_mut().entity_mut(cam).insert(FloatingOrigin);
+		let ship = spawn(&mut app, FixVec3::new(20000, 0, 0));
+		app.update();
+
+		//	Within the re-centre distance the origin holds still
+		app.world_mut().get_mut::<WorldPose>(cam).unwrap().pos = FixVec3::new(9000, 0, 0);
+		app.update();
+		assert_eq!(app.world().resource::<RenderOrigin>().pos, FixVec3::ZERO);
+		assert_eq!(translation(&app, cam), Vec3::new(9000., 0., 0.));
+
+		//	Past it, everything shifts in the same frame and relative offsets hold
+		app.world_mut().get_mut::<WorldPose>(cam).unwrap().pos = FixVec3::new(19000, 0, 0);
+		app.update();
+		assert_eq!(app.world().resource::<RenderOrigin>().pos, FixVec3::new(19000, 0, 0));
+		assert_eq!(translation(&app, cam), Vec3::ZERO);
+		assert_eq!(translation(&app, ship), Vec3::new(1000., 0., 0.));
+	}
+
+	#[test] fn test_rotation_and_children() {
+		let mut app = app();
+		let rot = FixQuat::from_dquat(bevy::math::DQuat::from_rotation_z(0.5));
+		let root = app.world_mut().spawn((WorldPose::new(FixVec3::new(5, 0, 0), rot), Transform::default())).id();
+		let child = app.world_mut().spawn((WorldPose::from_pos(FixVec3::new(7, 0, 0)), Transform::default(), ChildOf(root))).id();
+		app.update();
+
+		let t = app.world().get::<Transform>(root).unwrap();
+		assert!(t.rotation.abs_diff_eq(Quat::from_rotation_z(0.5), 1e-6));
+
+		//	The child sits 2 m out along world x, in the parent's turned axes
+		let t = *app.world().get::<Transform>(child).unwrap();
+		assert!(t.translation.abs_diff_eq(Vec3::new(2. * 0.5f32.cos(), -2. * 0.5f32.sin(), 0.), 1e-4));
+		assert!(t.rotation.abs_diff_eq(Quat::from_rotation_z(-0.5), 1e-6));
+
+		//	Moving only the parent rewrites the child
+		app.world_mut().get_mut::<WorldPose>(root).unwrap().rot = FixQuat::IDENTITY;
+		app.update();
+		assert!(translation(&app, child).abs_diff_eq(Vec3::new(2., 0., 0.), 1e-4));
+	}
+
+	#[test] fn test_child_of_unposed() {
+		let mut app = app();
+		let root = app.world_mut().spawn(Transform::default()).id();
+		let child = app.world_mut().spawn((WorldPose::from_pos(FixVec3::new(7, 0, 0)), Transform::from_xyz(1., 2., 3.), ChildOf(root))).id();
+		app.update();
+		assert_eq!(translation(&app, child), Vec3::new(1., 2., 3.));
+	}
+}
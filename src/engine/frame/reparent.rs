@@ -0,0 +1,307 @@
+//  	Imports
+use bevy::prelude::*;
+use bevy::math::DVec3;
+
+use crate::engine::math::{FixVec3, FixQuat, FixAngles};
+use super::pose::{WorldPose, WorldTwist, ParentFrame, LocalPose, LocalTwist};
+use super::propagate::{Placement, place};
+
+//		Re-parenting
+//	Moves an entity under another `ParentFrame` without moving it in the world:
+//	sphere-of-influence changes, docking, grabbing. The new `LocalPose` and
+//	`LocalTwist` are solved from the current world placement, but an f64 local
+//	pose composed back through fixed point lands within an ulp of where it
+//	started, not on it. What it misses by is kept as a `ReparentResidual` and
+//	added back on every propagation, so the world placement is unchanged bit for
+//	bit, and from then on the entity moves with its parent.
+//
+//	An entity below a root without a `WorldTwist` has none either; its own
+//	`LocalTwist`, relative to the old parent, is carried over to the new one.
+//	Without a `WorldPose` on both sides there is nothing to preserve and only
+//	`ParentFrame` changes. Re-parenting under a descendant makes a cycle, which
+//	propagation reports as `FrameFault::Cycle`.
+pub trait ReparentFrame {
+	fn reparent_frame(&mut self, parent: Entity) -> &mut Self;
+}
+
+impl ReparentFrame for EntityCommands<'_> {
+	fn reparent_frame(&mut self, parent: Entity) -> &mut Self {
+		self.queue(move |mut entity: EntityWorldMut| {
+			let (pose, twist) = (entity.get::<WorldPose>().copied(), entity.get::<WorldTwist>().copied());
+			let (parent_pose, parent_twist) = (entity.world().get::<WorldPose>(parent).copied(), entity.world().get::<WorldTwist>(parent).copied());
+			let (Some(pose), Some(parent_pose)) = (pose, parent_pose) else {
+				entity.insert(ParentFrame(parent)).remove::<ReparentResidual>();
+				return;
+			};
+
+			//	Below a still root: the motion relative to the old parent is all of it
+			let twist = twist.or_else(|| {
+				let local_twist = entity.get::<LocalTwist>()?;
+				let old = entity.get::<ParentFrame>().and_then(|old| entity.world().get::<WorldPose>(old.0));
+				Some(WorldTwist::default().compose(&old.copied().unwrap_or_default(), &pose, local_twist))
+			});
+
+			let local: LocalPose = pose.relative_to(&parent_pose);
+			let local_twist = twist.map(|twist| {
+				twist.relative_to(&parent_twist.unwrap_or_default(), &parent_pose, &pose)
+			});
+			let composed = place(&(parent_pose, parent_twist), &local, local_twist.as_ref());
+			let residual = ReparentResidual::between(parent, &(pose, twist), &composed);
+			entity.insert((ParentFrame(parent), local, residual));
+			match local_twist {
+				Some(local_twist) => { entity.insert(local_twist); },
+				None => { entity.remove::<LocalTwist>(); },
+			}
+		})
+	}
+}
+
+//	What the local placement solved by `reparent_frame` composes short of the
+//	world placement it was solved from, under the parent it was solved against.
+//	Positions, rotations and velocities are fixed point, where adding the
+//	difference back is exact. Angular velocities are f64, where it is exact
+//	while the two agree to within a factor of two (Sterbenz), as rounding leaves
+//	them. Dropped by propagation once the entity has another parent.
+//
+//	It is kept in world axes and added to each freshly composed placement, so
+//	under a parent that only moves, the entity moves exactly as it does. Under
+//	one that turns, the residual does not turn along: the entity stays within
+//	a few fixed-point steps of rigidly attached, and its rotation a few ulps off
+//	unit length, without that building up over time.
+#[derive(Clone, Copy, Debug, PartialEq, Component)]
+pub struct ReparentResidual {
+	parent: Entity,
+	pos: FixVec3,
+	rot: [FixAngles; 4],
+	lin: FixVec3,
+	ang: DVec3,
+}
+
+impl ReparentResidual {
+	fn between(parent: Entity, (pose, twist): &Placement, (composed, composed_twist): &Placement) -> Self {
+		let (q, c) = (pose.rot, composed.rot);
+		let (lin, ang) = match (twist, composed_twist) {
+			(Some(twist), Some(composed)) => (twist.lin - composed.lin, twist.ang - composed.ang),
+			_ => (FixVec3::ZERO, DVec3::ZERO),
+		};
+		Self { parent, pos: pose.pos - composed.pos, rot: [q.x - c.x, q.y - c.y, q.z - c.z, q.w - c.w], lin, ang }
+	}
+
+	#[inline] pub fn parent(&self) -> Entity { self.parent }
+
+	//	Moves a placement composed under `parent` by the residual
+	pub(crate) fn apply(&self, (pose, twist): &mut Placement) {
+		let (q, [x, y, z, w]) = (pose.rot, self.rot);
+		pose.pos += self.pos;
+		pose.rot = FixQuat::from_xyzw(q.x + x, q.y + y, q.z + z, q.w + w);
+		if let Some(twist) = twist {
+			twist.lin += self.lin;
+			twist.ang += self.ang;
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy::math::DQuat;
+	use crate::engine::frame::propagate::FramePropagationPlugin;
+	use crate::engine::math::FixOrigin;
+
+	struct System { app: App, sun: Entity, moon: Entity, ship: Entity }
+
+	//	Sun, planet, moon and ship, every frame turned and moving
+	fn system() -> System {
+		let mut app = App::new();
+		app.add_plugins(FramePropagationPlugin);
+		let world = app.world_mut();
+		let sun = world.spawn((
+			WorldPose::new(FixVec3::new(-3e9, 1e9, 5e8), FixQuat::from_dquat(DQuat::from_rotation_z(0.2))),
+			WorldTwist { lin: FixVec3::new(0., 2e4, 0.), ang: DVec3::new(0., 1e-9, 0.) },
+		)).id();
+		let planet = world.spawn((
+			ParentFrame(sun),
+			LocalPose { pos: DVec3::new(1e11, 2e10, 0.), rot: FixQuat::from_dquat(DQuat::from_rotation_x(0.3)) },
+			LocalTwist { lin: DVec3::new(3e4, 0., 0.), ang: DVec3::new(0., 0., 1e-5) },
+		)).id();
+		let moon = world.spawn((
+			ParentFrame(planet),
+			LocalPose { pos: DVec3::new(3.8e8, 0., 0.), rot: FixQuat::from_dquat(DQuat::from_rotation_z(1.1)) },
+			LocalTwist { lin: DVec3::new(0., 1e3, 0.), ang: DVec3::new(0., 0., 2.6e-6) },
+		)).id();
+		let ship = world.spawn((
+			ParentFrame(planet),
+			LocalPose { pos: DVec3::new(7e6, 1e5, -3e4), rot: FixQuat::from_dquat(DQuat::from_rotation_y(-0.4)) },
+			LocalTwist { lin: DVec3::new(-50., 7.5e3, 20.), ang: DVec3::new(0.01, 0., 0.) },
+		)).id();
+		app.update();
+		System { app, sun, moon, ship }
+	}
+
+	fn placement(app: &App, e: Entity) -> (WorldPose, WorldTwist) {
+		let world = app.world();
+		(*world.get::<WorldPose>(e).unwrap(), *world.get::<WorldTwist>(e).unwrap())
+	}
+
+	fn reparent(app: &mut App, e: Entity, parent: Entity) {
+		app.world_mut().commands().entity(e).reparent_frame(parent);
+		app.world_mut().flush();
+	}
+
+	#[test] fn test_exact() {
+		//	Into the moon's sphere of influence, and out into the sun's, where the
+		//	offset is too long for an f64 local pose to land back on the fixed-point
+		//	grid
+		for into_sun in [false, true] {
+			let System { mut app, sun, moon, ship } = system();
+			let parent = if into_sun { sun } else { moon };
+			let before = placement(&app, ship);
+			reparent(&mut app, ship, parent);
+			for _ in 0..3 {
+				app.update();
+				assert_eq!(placement(&app, ship), before);
+			}
+			assert_eq!(app.world().get::<ParentFrame>(ship), Some(&ParentFrame(parent)));
+
+			//	The solved local placement is what the residual makes exact
+			let (parent_pose, parent_twist) = placement(&app, parent);
+			let local = *app.world().get::<LocalPose>(ship).unwrap();
+			let local_twist = *app.world().get::<LocalTwist>(ship).unwrap();
+			let pose = parent_pose.compose(&local);
+			assert!(pose.pos.distance(before.0.pos) <= FixOrigin::DELTA * 4);
+			assert!(pose.rot.to_dquat().abs_diff_eq(before.0.rot.to_dquat(), 1e-12));
+			let twist = parent_twist.compose(&parent_pose, &pose, &local_twist);
+			assert!(twist.lin.to::<DVec3>().abs_diff_eq(before.1.lin.to::<DVec3>(), 1e-3));
+			assert!(twist.ang.abs_diff_eq(before.1.ang, 1e-15));
+			let residual = *app.world().get::<ReparentResidual>(ship).unwrap();
+			assert_eq!(residual.pos != FixVec3::ZERO, into_sun);
+		}
+	}
+
+	#[test] fn test_moving_parent() {
+		for into_sun in [false, true] {
+			let System { mut app, sun, moon, ship } = system();
+			let parent = if into_sun { sun } else { moon };
+			//	The new parent slides along every frame, turned as it is
+			app.add_systems(Update, move |mut locals: Query<&mut LocalPose>, mut poses: Query<&mut WorldPose>| {
+				match locals.get_mut(parent) {
+					Ok(mut local) => local.pos.x += 1000.,
+					Err(_) => poses.get_mut(parent).unwrap().pos.x += FixOrigin::from_num(1000),
+				}
+			});
+			reparent(&mut app, ship, parent);
+
+			//	From the frame it is re-parented in, the ship moves exactly as its
+			//	parent does: not an ulp more, which a dropped residual would show
+			for _ in 0..3 {
+				let (before, parent_before) = (placement(&app, ship), placement(&app, parent));
+				app.update();
+				let (after, parent_after) = (placement(&app, ship), placement(&app, parent));
+				assert_ne!(parent_after.0.pos, parent_before.0.pos);
+				assert_eq!(after.0.pos - before.0.pos, parent_after.0.pos - parent_before.0.pos);
+				assert_eq!(after.0.rot, before.0.rot);
+				assert_eq!(after.1.lin - before.1.lin, parent_after.1.lin - parent_before.1.lin);
+				assert_eq!(after.1.ang, before.1.ang);
+			}
+		}
+	}
+
+	#[test] fn test_spinning_parent() {
+		let System { mut app, sun, ship, .. } = system();
+		//	The sun turns a milliradian about its Z every frame
+		let spin = FixQuat::from_dquat(DQuat::from_rotation_z(1e-3));
+		app.add_systems(Update, move |mut poses: Query<&mut WorldPose>| {
+			let mut pose = poses.get_mut(sun).unwrap();
+			pose.rot = (pose.rot * spin).normalize();
+		});
+		reparent(&mut app, ship, sun);
+		let residual = *app.world().get::<ReparentResidual>(ship).unwrap();
+		let local = *app.world().get::<LocalPose>(ship).unwrap();
+		let start = placement(&app, sun).0.rot;
+
+		//	The residual is added as it was solved, in world axes: the ship stays
+		//	within a few steps of rigidly attached, however long the sun turns
+		for _ in 0..100 {
+			app.update();
+			let (pose, rigid) = (placement(&app, ship).0, placement(&app, sun).0.compose(&local));
+			assert_eq!(pose.pos - rigid.pos, residual.pos);
+			assert!(pose.pos.distance(rigid.pos) <= FixOrigin::DELTA * 4);
+			assert!(pose.rot.to_dquat().abs_diff_eq(rigid.rot.to_dquat(), 1e-15));
+			assert!((pose.rot.to_dquat().length() - 1.).abs() < 1e-15);
+		}
+		let turned = placement(&app, sun).0.rot.to_dquat().angle_between(start.to_dquat());
+		assert!((turned - 0.1).abs() < 1e-6);
+	}
+
+	#[test] fn test_still_root() {
+		//	A station with no twist, and a rover driving and turning on it
+		let System { mut app, moon, .. } = system();
+		let station_pose = WorldPose::new(FixVec3::new(5e10, -2e9, 0.), FixQuat::from_dquat(DQuat::from_rotation_z(0.5)));
+		let station = app.world_mut().spawn(station_pose).id();
+		let rover_twist = LocalTwist { lin: DVec3::new(10., 0., 0.), ang: DVec3::new(0., 0., 0.1) };
+		let rover = app.world_mut().spawn((ParentFrame(station), LocalPose::from_pos(DVec3::new(100., 0., 0.)), rover_twist)).id();
+		app.update();
+		assert!(app.world().get::<WorldTwist>(rover).is_none());
+
+		//	Under the moon it keeps its own motion, now on top of the moon's
+		let pose = *app.world().get::<WorldPose>(rover).unwrap();
+		let expect = WorldTwist::default().compose(&station_pose, &pose, &rover_twist);
+		reparent(&mut app, rover, moon);
+		app.update();
+		assert!(app.world().get::<LocalTwist>(rover).is_some());
+		assert_eq!(*app.world().get::<WorldPose>(rover).unwrap(), pose);
+		assert_eq!(*app.world().get::<WorldTwist>(rover).unwrap(), expect);
+		assert!(expect.lin.to::<DVec3>().abs_diff_eq(DVec3::new(0.5f64.cos(), 0.5f64.sin(), 0.) * 10., 1e-4));
+
+		//	Under another still root, it is turned into that root's axes
+		let dock = app.world_mut().spawn(WorldPose::default()).id();
+		let other = app.world_mut().spawn((ParentFrame(station), LocalPose::default(), rover_twist)).id();
+		app.update();
+		reparent(&mut app, other, dock);
+		let local_twist = *app.world().get::<LocalTwist>(other).unwrap();
+		assert!(local_twist.lin.abs_diff_eq(expect.lin.to::<DVec3>(), 1e-4));
+		assert!(local_twist.ang.abs_diff_eq(rover_twist.ang, 1e-15));
+	}
+
+	#[test] fn test_reattached() {
+		//	A residual is only good under the parent it was solved against
+		let System { mut app, moon, ship, .. } = system();
+		let planet = app.world().get::<ParentFrame>(ship).unwrap().0;
+		reparent(&mut app, ship, moon);
+		app.update();
+		assert!(app.world().get::<ReparentResidual>(ship).is_some());
+		app.world_mut().get_mut::<ParentFrame>(ship).unwrap().0 = planet;
+		app.update();
+		assert!(app.world().get::<ReparentResidual>(ship).is_none());
+		let local = *app.world().get::<LocalPose>(ship).unwrap();
+		assert_eq!(placement(&app, ship).0, placement(&app, planet).0.compose(&local));
+	}
+
+	#[test] fn test_round_trip() {
+		let System { mut app, moon, ship, .. } = system();
+		let planet = app.world().get::<ParentFrame>(ship).unwrap().0;
+		let local = *app.world().get::<LocalPose>(ship).unwrap();
+		let local_twist = *app.world().get::<LocalTwist>(ship).unwrap();
+		reparent(&mut app, ship, moon);
+		app.update();
+		reparent(&mut app, ship, planet);
+		app.update();
+
+		let back = *app.world().get::<LocalPose>(ship).unwrap();
+		let back_twist = *app.world().get::<LocalTwist>(ship).unwrap();
+		assert!(back.pos.abs_diff_eq(local.pos, 1e-4) && back.rot.to_dquat().abs_diff_eq(local.rot.to_dquat(), 1e-12));
+		assert!(back_twist.lin.abs_diff_eq(local_twist.lin, 1e-3) && back_twist.ang.abs_diff_eq(local_twist.ang, 1e-12));
+	}
+
+	#[test] fn test_unplaced() {
+		let System { mut app, moon, .. } = system();
+		let loose = app.world_mut().spawn(LocalPose::default()).id();
+		reparent(&mut app, loose, moon);
+		assert_eq!(app.world().get::<ParentFrame>(loose), Some(&ParentFrame(moon)));
+		assert!(app.world().get::<ReparentResidual>(loose).is_none());
+		app.update();
+		let (pose, moon_pose) = (app.world().get::<WorldPose>(loose).unwrap(), placement(&app, moon).0);
+		assert_eq!(pose.pos, moon_pose.pos);
+		assert!(pose.rot.to_dquat().abs_diff_eq(moon_pose.rot.to_dquat(), 1e-15));
+	}
+}
@@ -0,0 +1,910 @@
+//  	Imports
+use bevy::math::{DQuat, DVec3};
+use bevy::reflect::{Reflect, std_traits::ReflectDefault};
+#[cfg(feature = "serde")]
+use bevy::reflect::{ReflectSerialize, ReflectDeserialize};
+
+use std::fmt::Debug;
+use std::ops::{Neg, Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign};
+
+use az::Cast;
+use fixed::FixedI128;
+use fixed::types::{I2F62, I48F16, extra::U62};
+
+use super::fixmath;
+use super::scalar::{Scalar, Real};
+
+//		Abstractions
+//	Vector
+pub trait TypeVec3:
+	//	Rust traits
+	Clone + Copy + Debug + Default + PartialEq + Reflect
+	+ Send + Sync + 'static
+	//	Algebraic traits
+	+ Neg<Output = Self>
+	+ Add<Output = Self> + AddAssign
+	+ Sub<Output = Self> + SubAssign
+	+ Mul<Self::Scalar, Output = Self> + MulAssign<Self::Scalar>
+	+ Div<Self::Scalar, Output = Self> + DivAssign<Self::Scalar>
+{
+	type Scalar: Scalar + Cast<Self::Scalar>;
+
+	//	Constructors
+	fn new<N>(x: N, y: N, z: N) -> Self
+		where N: Cast<Self::Scalar>;
+	fn zero() -> Self;
+
+	//	Access/conversion
+	fn x(&self) -> Self::Scalar;
+	fn y(&self) -> Self::Scalar;
+	fn z(&self) -> Self::Scalar;
+
+	fn to_f64(self) -> DVec3;
+
+	//	Algebraic operations
+	fn dot(self, other: Self) -> Self::Scalar;
+	fn cross(self, other: Self) -> Self;
+	fn mag2(self) -> Self::Scalar;
+
+	//	Metric operations
+	fn length(self) -> Self::Scalar;
+	fn distance(self, other: Self) -> Self::Scalar;
+	fn normalize_or_zero(self) -> Self;
+	fn angle_between(self, other: Self) -> Self::Scalar;
+	fn project_onto(self, onto: Self) -> Self;
+	fn reject_from(self, from: Self) -> Self;
+
+	//	Rotations
+	fn rotate(self, rot: FixQuat) -> Self;
+}
+
+//		Fixed-point math
+//	Overflow policy
+//	Plain fixed-point arithmetic panics in debug builds and wraps in release, so
+//	the same simulation could diverge between the two. The vector operators
+//	(and `dot`/`cross`) follow one policy in every profile instead: panic by
+//	default, or saturate/wrap with the `fixed-saturating`/`fixed-wrapping`
+//	features. Features are additive, so with both enabled saturation wins. The
+//	`checked_*`, `saturating_*` and `wrapping_*` methods are always available
+//	whatever the policy.
+#[cfg(not(any(feature = "fixed-saturating", feature = "fixed-wrapping")))]
+macro_rules! overflow_policy {
+	($checked:expr, $saturating:expr, $wrapping:expr) => { $checked.expect("fixed-point vector overflow") };
+}
+#[cfg(feature = "fixed-saturating")]
+macro_rules! overflow_policy {
+	($checked:expr, $saturating:expr, $wrapping:expr) => { $saturating };
+}
+#[cfg(all(feature = "fixed-wrapping", not(feature = "fixed-saturating")))]
+macro_rules! overflow_policy {
+	($checked:expr, $saturating:expr, $wrapping:expr) => { $wrapping };
+}
+
+//	Vector Macro
+macro_rules! define_fixed_vec3 {
+	//	1)	public-facing macro
+	//	Creates *all* vector ops, even those between other types
+	($Name:ident, $Scalar:ty, $WideName:ident, $Wide:ty) => {
+		//	Internal struct defs
+		define_fixed_vec3!(@struct_def $Name, $Scalar, $WideName, $Wide);
+		define_fixed_vec3!(@impl_ops $Name, $Scalar);
+		define_fixed_vec3!(@impl_overflow $Name, $Scalar);
+		define_fixed_vec3!(@impl_trait $Name, $Scalar);
+
+		define_fixed_vec3!(@struct_def $WideName, $Wide, $WideName, $Wide);
+		define_fixed_vec3!(@impl_ops $WideName, $Wide);
+		define_fixed_vec3!(@impl_overflow $WideName, $Wide);
+		define_fixed_vec3!(@impl_trait $WideName, $Wide);
+	};
+
+	//	2)	boilerplate: internal struct generator
+	//	Creates fixed-point specific vector ops
+	(@struct_def $Name:ident, $Scalar:ty, $WideName:ty, $Wide:ty) => {
+		//	Opaque to reflection, as the fixed-point scalars have no `Reflect`
+		#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+		#[reflect(opaque, Clone, Debug, Default, PartialEq)]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+		#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+		pub struct $Name {
+			pub x: $Scalar,
+			pub y: $Scalar,
+			pub z: $Scalar,
+		}
+
+		impl $Name {
+			//		Constructors
+			//	No zero constructor - easy to define const
+			pub const ZERO: Self = Self { x: <$Scalar>::ZERO, y: <$Scalar>::ZERO, z: <$Scalar>::ZERO };
+
+			//	boilerplate: new() constructor accepts any type with trait ToFixed
+			#[inline(always)] pub fn new<N>(x: N, y: N, z: N) -> Self 
+				where N: Cast<$Scalar> { Self { x: x.cast(), y: y.cast(), z: z.cast() } }
+
+			//		Access/conversion
+			//	boilerplate: to() constructor casts to any type with trait FromFixed
+			#[inline] pub fn to<V>(self) -> V where 
+				V: TypeVec3, $Scalar: Cast<V::Scalar>, 
+			{ V::new( self.x.cast(), self.y.cast(), self.z.cast(), ) }
+
+			//		Algebraic ops
+			//	Follow the overflow policy
+			#[inline] pub fn dot(self, other: Self) -> $Scalar {
+				overflow_policy!(self.checked_dot(other), self.saturating_dot(other), self.wrapping_dot(other))
+			}
+			#[inline] pub fn cross(self, other: Self) -> Self {
+				overflow_policy!(self.checked_cross(other), self.saturating_cross(other), self.wrapping_cross(other))
+			}
+
+			//	Same, in the wide type: products of positions and velocities that
+			//	overflow the scalar (r × v at interplanetary distances) fit there,
+			//	and the inputs widen losslessly.
+			#[inline] pub fn dot_wide(self, other: Self) -> $Wide {
+				self.to::<$WideName>().dot(other.to())
+			}
+			#[inline] pub fn cross_wide(self, other: Self) -> $WideName {
+				self.to::<$WideName>().cross(other.to())
+			}
+
+			//		Metric ops
+			//	Lengths come straight from the raw bits (see `fixmath::hypot3`), and
+			//	directions are taken at the wide type's precision, so none of these
+			//	square a coordinate in the scalar type: they hold at any distance it
+			//	can store.
+
+			//	Saturates at MAX, which only a vector within √3 of the range limit reaches
+			#[inline] pub fn length(self) -> $Scalar {
+				fixmath::hypot3(self.x, self.y, self.z).unwrap_or(<$Scalar>::MAX)
+			}
+			#[inline] pub fn distance(self, other: Self) -> $Scalar { (other - self).length() }
+
+			//	Unit vector along `self`; zero for the zero vector
+			#[inline] pub fn normalize_or_zero(self) -> Self { self.unit_wide().to() }
+
+			//	Unsigned angle in radians, in [0, π]; zero if either vector is zero
+			pub fn angle_between(self, other: Self) -> $Scalar {
+				let (a, b) = (self.unit_wide(), other.unit_wide());
+				let c = a.cross(b);
+				//	|a × b| <= 1
+				let sin = fixmath::hypot3(c.x, c.y, c.z).unwrap_or(<$Wide>::ONE);
+				<$Wide as Real>::atan2(sin, a.dot(b)).cast()
+			}
+
+			//	Component of `self` along `onto`, and what is left; projecting onto
+			//	the zero vector gives zero.
+			pub fn project_onto(self, onto: Self) -> Self {
+				let (u, v): ($WideName, $WideName) = (onto.unit_wide(), self.to());
+				(u * v.dot(u)).to()
+			}
+			#[inline] pub fn reject_from(self, from: Self) -> Self { self - self.project_onto(from) }
+
+			fn unit_wide(self) -> $WideName {
+				let mut v: $WideName = self.to();
+				loop {
+					match fixmath::hypot3(v.x, v.y, v.z) {
+						Some(len) if len == <$Wide>::ZERO => return v,
+						Some(len) => return v / len,
+						//	Past the wide range (wide vectors only): direction survives dropping low bits
+						None => v = <$WideName>::new(v.x >> 2, v.y >> 2, v.z >> 2),
+					}
+				}
+			}
+		}
+	};
+
+	//	3)	boilerplate: operations
+	//	Operators follow the overflow policy
+	(@impl_ops $Name:ident, $Scalar:ty) => {
+		//	-Vector
+		impl Neg for $Name {
+			type Output = Self; 
+			#[inline] fn neg(self) -> Self {
+				overflow_policy!(self.checked_neg(), self.saturating_neg(), self.wrapping_neg())
+			}
+		}
+
+		//	Vector +- Vector
+		impl Add for $Name {
+			type Output = Self; 
+			#[inline] fn add(self, rhs: Self) -> Self {
+				overflow_policy!(self.checked_add(rhs), self.saturating_add(rhs), self.wrapping_add(rhs))
+			}
+		}
+		impl AddAssign for $Name {
+			#[inline] fn add_assign(&mut self, rhs: Self) { *self = *self + rhs; }
+		}
+
+		impl Sub for $Name {
+			type Output = Self; 
+			#[inline] fn sub(self, rhs: Self) -> Self {
+				overflow_policy!(self.checked_sub(rhs), self.saturating_sub(rhs), self.wrapping_sub(rhs))
+			}
+		}
+		impl SubAssign for $Name {
+			#[inline] fn sub_assign(&mut self, rhs: Self) { *self = *self - rhs; }
+		}
+
+		//	Vector */ Scalar
+		impl Mul<$Scalar> for $Name {
+			type Output = Self; 
+			#[inline] fn mul(self, rhs: $Scalar) -> Self {
+				overflow_policy!(self.checked_mul(rhs), self.saturating_mul(rhs), self.wrapping_mul(rhs))
+			}
+		}
+		impl MulAssign<$Scalar> for $Name {
+			#[inline] fn mul_assign(&mut self, rhs: $Scalar) { *self = *self * rhs; }
+		}
+
+		//	Division by zero panics under every policy
+		impl Div<$Scalar> for $Name { 
+			type Output = Self;
+			#[inline] fn div(self, rhs: $Scalar) -> Self {
+				overflow_policy!(self.checked_div(rhs), self.saturating_div(rhs), self.wrapping_div(rhs))
+			}
+		}
+		impl DivAssign<$Scalar> for $Name { 
+			#[inline] fn div_assign(&mut self, rhs: $Scalar) { *self = *self / rhs; }
+		}
+	};
+
+	//	4)	boilerplate: explicit overflow handling
+	//	Checked ops return None if any component overflows (or on division by zero)
+	(@impl_overflow $Name:ident, $Scalar:ty) => {
+		impl $Name {
+			//		Checked
+			#[inline] pub fn checked_neg(self) -> Option<Self> {
+				Some(Self { x: self.x.checked_neg()?, y: self.y.checked_neg()?, z: self.z.checked_neg()? })
+			}
+			#[inline] pub fn checked_add(self, rhs: Self) -> Option<Self> {
+				Some(Self { x: self.x.checked_add(rhs.x)?, y: self.y.checked_add(rhs.y)?, z: self.z.checked_add(rhs.z)? })
+			}
+			#[inline] pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+				Some(Self { x: self.x.checked_sub(rhs.x)?, y: self.y.checked_sub(rhs.y)?, z: self.z.checked_sub(rhs.z)? })
+			}
+			#[inline] pub fn checked_mul(self, rhs: $Scalar) -> Option<Self> {
+				Some(Self { x: self.x.checked_mul(rhs)?, y: self.y.checked_mul(rhs)?, z: self.z.checked_mul(rhs)? })
+			}
+			#[inline] pub fn checked_div(self, rhs: $Scalar) -> Option<Self> {
+				Some(Self { x: self.x.checked_div(rhs)?, y: self.y.checked_div(rhs)?, z: self.z.checked_div(rhs)? })
+			}
+			#[inline] pub fn checked_dot(self, other: Self) -> Option<$Scalar> {
+				self.x.checked_mul(other.x)?
+					.checked_add(self.y.checked_mul(other.y)?)?
+					.checked_add(self.z.checked_mul(other.z)?)
+			}
+			#[inline] pub fn checked_cross(self, other: Self) -> Option<Self> {
+				let det = |a: $Scalar, b: $Scalar, c: $Scalar, d: $Scalar| a.checked_mul(b)?.checked_sub(c.checked_mul(d)?);
+				Some(Self {
+					x: det(self.y, other.z, self.z, other.y)?,
+					y: det(self.z, other.x, self.x, other.z)?,
+					z: det(self.x, other.y, self.y, other.x)?,
+				})
+			}
+
+			//		Saturating
+			//	Each product and sum saturates in turn: a dot or cross whose terms
+			//	saturate in opposite directions is not clamped to the true result.
+			#[inline] pub fn saturating_neg(self) -> Self {
+				Self { x: self.x.saturating_neg(), y: self.y.saturating_neg(), z: self.z.saturating_neg() }
+			}
+			#[inline] pub fn saturating_add(self, rhs: Self) -> Self {
+				Self { x: self.x.saturating_add(rhs.x), y: self.y.saturating_add(rhs.y), z: self.z.saturating_add(rhs.z) }
+			}
+			#[inline] pub fn saturating_sub(self, rhs: Self) -> Self {
+				Self { x: self.x.saturating_sub(rhs.x), y: self.y.saturating_sub(rhs.y), z: self.z.saturating_sub(rhs.z) }
+			}
+			#[inline] pub fn saturating_mul(self, rhs: $Scalar) -> Self {
+				Self { x: self.x.saturating_mul(rhs), y: self.y.saturating_mul(rhs), z: self.z.saturating_mul(rhs) }
+			}
+			#[inline] pub fn saturating_div(self, rhs: $Scalar) -> Self {
+				Self { x: self.x.saturating_div(rhs), y: self.y.saturating_div(rhs), z: self.z.saturating_div(rhs) }
+			}
+			#[inline] pub fn saturating_dot(self, other: Self) -> $Scalar {
+				self.x.saturating_mul(other.x)
+					.saturating_add(self.y.saturating_mul(other.y))
+					.saturating_add(self.z.saturating_mul(other.z))
+			}
+			#[inline] pub fn saturating_cross(self, other: Self) -> Self {
+				let det = |a: $Scalar, b: $Scalar, c: $Scalar, d: $Scalar| a.saturating_mul(b).saturating_sub(c.saturating_mul(d));
+				Self {
+					x: det(self.y, other.z, self.z, other.y),
+					y: det(self.z, other.x, self.x, other.z),
+					z: det(self.x, other.y, self.y, other.x),
+				}
+			}
+
+			//		Wrapping
+			#[inline] pub fn wrapping_neg(self) -> Self {
+				Self { x: self.x.wrapping_neg(), y: self.y.wrapping_neg(), z: self.z.wrapping_neg() }
+			}
+			#[inline] pub fn wrapping_add(self, rhs: Self) -> Self {
+				Self { x: self.x.wrapping_add(rhs.x), y: self.y.wrapping_add(rhs.y), z: self.z.wrapping_add(rhs.z) }
+			}
+			#[inline] pub fn wrapping_sub(self, rhs: Self) -> Self {
+				Self { x: self.x.wrapping_sub(rhs.x), y: self.y.wrapping_sub(rhs.y), z: self.z.wrapping_sub(rhs.z) }
+			}
+			#[inline] pub fn wrapping_mul(self, rhs: $Scalar) -> Self {
+				Self { x: self.x.wrapping_mul(rhs), y: self.y.wrapping_mul(rhs), z: self.z.wrapping_mul(rhs) }
+			}
+			#[inline] pub fn wrapping_div(self, rhs: $Scalar) -> Self {
+				Self { x: self.x.wrapping_div(rhs), y: self.y.wrapping_div(rhs), z: self.z.wrapping_div(rhs) }
+			}
+			#[inline] pub fn wrapping_dot(self, other: Self) -> $Scalar {
+				self.x.wrapping_mul(other.x)
+					.wrapping_add(self.y.wrapping_mul(other.y))
+					.wrapping_add(self.z.wrapping_mul(other.z))
+			}
+			#[inline] pub fn wrapping_cross(self, other: Self) -> Self {
+				let det = |a: $Scalar, b: $Scalar, c: $Scalar, d: $Scalar| a.wrapping_mul(b).wrapping_sub(c.wrapping_mul(d));
+				Self {
+					x: det(self.y, other.z, self.z, other.y),
+					y: det(self.z, other.x, self.x, other.z),
+					z: det(self.x, other.y, self.y, other.x),
+				}
+			}
+		}
+	};
+
+	//	5)	boilerplate: TypeVec3 forwarding to the inherent ops
+	(@impl_trait $Name:ident, $Scalar:ty) => {
+		impl TypeVec3 for $Name {
+			type Scalar = $Scalar;
+
+			//	Constructors
+			#[inline] fn new<N>(x: N, y: N, z: N) -> Self 
+				where N: Cast<$Scalar> { Self::new(x, y, z) }
+			#[inline(always)] fn zero() -> Self { Self::ZERO }
+
+			//	Access/conversion
+			#[inline(always)] fn x(&self) -> $Scalar { self.x }
+			#[inline(always)] fn y(&self) -> $Scalar { self.y }
+			#[inline(always)] fn z(&self) -> $Scalar { self.z }
+
+			#[inline] fn to_f64(self) -> DVec3 {
+				DVec3::new(self.x.to_num(), self.y.to_num(), self.z.to_num())
+			}
+
+			//	Arithmetic ops
+			#[inline] fn dot(self, other: Self) -> $Scalar { self.dot(other) }
+			#[inline] fn cross(self, other: Self) -> Self { self.cross(other) }
+			//	Follows the overflow policy like `dot`: squared distances outgrow the
+			//	scalar long before distances do, so far vectors want `length` or
+			//	`dot_wide` instead.
+			#[inline] fn mag2(self) -> $Scalar { self.dot(self) }
+
+			//	Metric ops
+			#[inline] fn length(self) -> $Scalar { self.length() }
+			#[inline] fn distance(self, other: Self) -> $Scalar { self.distance(other) }
+			#[inline] fn normalize_or_zero(self) -> Self { self.normalize_or_zero() }
+			#[inline] fn angle_between(self, other: Self) -> $Scalar { self.angle_between(other) }
+			#[inline] fn project_onto(self, onto: Self) -> Self { self.project_onto(onto) }
+			#[inline] fn reject_from(self, from: Self) -> Self { self.reject_from(from) }
+
+			//	Geometric ops
+			#[inline] fn rotate(self, rot: FixQuat) -> Self { rot * self }
+		}
+	};
+}
+
+//	Define types
+pub type FixOrigin = I48F16;
+pub type FixAngles = I2F62;
+pub type FixWide = FixedI128<U62>;
+
+define_fixed_vec3!(FixVec3, FixOrigin, FixVec3Wide, FixWide);
+//define_fixed_vec3!(FixAng3, FixAngles);
+
+//		Rotations
+//	Trigonometry
+//	FixWide radians in and out, through the deterministic series in `fixmath`
+fn sin_cos(angle: FixWide) -> (FixWide, FixWide) { fixmath::sin_cos(angle) }
+
+fn atan2(y: FixWide, x: FixWide) -> FixWide { fixmath::atan2(y, x).expect("angles in [-π, π] fit FixWide") }
+
+//	Quaternion
+//	Unit quaternion in FixAngles: the components of a unit quaternion lie in
+//	[-1, 1], which leaves a bit of headroom over 62 fractional bits. Products and
+//	rotations are carried out in FixWide, which shares the fractional bits, and
+//	vectors are rotated without leaving fixed point. Opaque to reflection, like
+//	the vectors.
+#[derive(Clone, Copy, Debug, PartialEq, Reflect)]
+#[reflect(opaque, Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", reflect(Serialize, Deserialize))]
+pub struct FixQuat {
+	pub x: FixAngles,
+	pub y: FixAngles,
+	pub z: FixAngles,
+	pub w: FixAngles,
+}
+
+impl Default for FixQuat {
+	fn default() -> Self { Self::IDENTITY }
+}
+
+impl FixQuat {
+	//		Constructors
+	pub const IDENTITY: Self = Self { x: FixAngles::ZERO, y: FixAngles::ZERO, z: FixAngles::ZERO, w: FixAngles::ONE };
+
+	#[inline] pub const fn from_xyzw(x: FixAngles, y: FixAngles, z: FixAngles, w: FixAngles) -> Self {
+		Self { x, y, z, w }
+	}
+
+	//	Right-handed rotation by `angle` radians about `axis`, which need not be
+	//	normalised; a zero axis gives the identity.
+	pub fn from_axis_angle<V>(axis: V, angle: FixWide) -> Self
+		where V: TypeVec3, V::Scalar: Cast<FixWide>
+	{
+		let axis = FixVec3Wide::new(axis.x(), axis.y(), axis.z()).normalize_or_zero();
+		let (s, c) = sin_cos(angle / 2);
+		//	Rounding leaves sin² + cos² a few ulps off 1
+		Self::from_wide([axis.x * s, axis.y * s, axis.z * s, c]).normalize()
+	}
+	#[inline] pub fn from_rotation_x(angle: FixWide) -> Self { Self::from_axis_angle(FixVec3Wide::new(1, 0, 0), angle) }
+	#[inline] pub fn from_rotation_y(angle: FixWide) -> Self { Self::from_axis_angle(FixVec3Wide::new(0, 1, 0), angle) }
+	#[inline] pub fn from_rotation_z(angle: FixWide) -> Self { Self::from_axis_angle(FixVec3Wide::new(0, 0, 1), angle) }
+
+	//	Intrinsic X, then Y, then Z: glam's `EulerRot::XYZ`
+	pub fn from_euler_xyz(x: FixWide, y: FixWide, z: FixWide) -> Self {
+		Self::from_rotation_x(x) * Self::from_rotation_y(y) * Self::from_rotation_z(z)
+	}
+
+	//		Access/conversion
+	//	Axis and angle in [0, 2π]; the identity reports the X axis
+	pub fn to_axis_angle(self) -> (FixVec3Wide, FixWide) {
+		let [x, y, z, w] = self.wide();
+		match fixmath::hypot3(x, y, z) {
+			Some(s) if s > FixWide::ZERO => (FixVec3Wide::new(x / s, y / s, z / s), atan2(s, w) * 2),
+			_ => (FixVec3Wide::new(1, 0, 0), FixWide::ZERO),
+		}
+	}
+
+	//	Inverse of `from_euler_xyz`; the middle angle is in [-π/2, π/2]
+	pub fn to_euler_xyz(self) -> (FixWide, FixWide, FixWide) {
+		let [x, y, z, w] = self.wide();
+		let two = FixWide::from_num(2);
+
+		//	Rotation matrix entries: m02 = sin y, and the rest give x and z
+		let m02 = (two * (x * z + w * y)).clamp(-FixWide::ONE, FixWide::ONE);
+		let m12 = two * (y * z - w * x);
+		let m22 = FixWide::ONE - two * (x * x + y * y);
+		let m01 = two * (x * y - w * z);
+		let m00 = FixWide::ONE - two * (y * y + z * z);
+
+		let cos_y = fixmath::sqrt(FixWide::ONE - m02 * m02);
+		(atan2(-m12, m22), atan2(m02, cos_y), atan2(-m01, m00))
+	}
+
+	//	Bevy interop: leaves the deterministic world, so keep it to the edges
+	//	(rendering, input, tooling).
+	pub fn from_dquat(q: DQuat) -> Self {
+		Self::from_wide([q.x.cast(), q.y.cast(), q.z.cast(), q.w.cast()]).normalize()
+	}
+	pub fn to_dquat(self) -> DQuat {
+		DQuat::from_xyzw(self.x.to_num(), self.y.to_num(), self.z.to_num(), self.w.to_num())
+	}
+
+	//		Algebraic ops
+	#[inline] pub fn dot(self, other: Self) -> FixWide {
+		let (a, b) = (self.wide(), other.wide());
+		a[0] * b[0] + a[1] * b[1] + a[2] * b[2] + a[3] * b[3]
+	}
+
+	//	Inverse of a unit quaternion
+	#[inline] pub fn conjugate(self) -> Self { Self { x: -self.x, y: -self.y, z: -self.z, w: self.w } }
+
+	//	Back onto the unit sphere, to undo the drift of repeated products; the
+	//	zero quaternion becomes the identity.
+	pub fn normalize(self) -> Self {
+		let q = self.wide();
+		match fixmath::rsqrt(self.dot(self)) {
+			Some(r) => Self::from_wide(q.map(|c| c * r)),
+			None => Self::IDENTITY,
+		}
+	}
+
+	//	Constant angular velocity from `self` (t = 0) to `end` (t = 1), along the
+	//	shorter arc.
+	pub fn slerp(self, end: Self, t: FixWide) -> Self {
+		let (a, mut b) = (self.wide(), end.wide());
+		let mut cos = self.dot(end);
+		if cos < FixWide::ZERO {
+			b = b.map(|c| -c);
+			cos = -cos;
+		}
+
+		//	sin θ vanishes for (nearly) equal rotations; lerp is exact enough there
+		let one = FixWide::ONE;
+		let (wa, wb) = if cos > one - FixWide::lit("0.000000000001") {
+			(one - t, t)
+		} else {
+			let sin = fixmath::sqrt(one - cos * cos);
+			let theta = atan2(sin, cos);
+			(sin_cos((one - t) * theta).0 / sin, sin_cos(t * theta).0 / sin)
+		};
+		Self::from_wide(std::array::from_fn(|i| a[i] * wa + b[i] * wb)).normalize()
+	}
+
+	//		Geometric ops
+	//	v + 2w(q × v) + 2q × (q × v)
+	pub fn rotate_wide(self, v: FixVec3Wide) -> FixVec3Wide {
+		let [x, y, z, w] = self.wide();
+		let q = FixVec3Wide { x, y, z };
+		let t = q.cross(v) * FixWide::from_num(2);
+		v + t * w + q.cross(t)
+	}
+
+	//	Components at full precision, and back
+	#[inline] fn wide(self) -> [FixWide; 4] { [self.x.cast(), self.y.cast(), self.z.cast(), self.w.cast()] }
+	#[inline] fn from_wide(q: [FixWide; 4]) -> Self { Self { x: q[0].cast(), y: q[1].cast(), z: q[2].cast(), w: q[3].cast() } }
+}
+
+//	Hamilton product: `a * b` rotates by b, then by a
+impl Mul for FixQuat {
+	type Output = Self;
+	fn mul(self, rhs: Self) -> Self {
+		let ([ax, ay, az, aw], [bx, by, bz, bw]) = (self.wide(), rhs.wide());
+		Self::from_wide([
+			aw * bx + ax * bw + ay * bz - az * by,
+			aw * by - ax * bz + ay * bw + az * bx,
+			aw * bz + ax * by - ay * bx + az * bw,
+			aw * bw - ax * bx - ay * by - az * bz,
+		])
+	}
+}
+
+impl Neg for FixQuat {
+	type Output = Self;
+	#[inline] fn neg(self) -> Self { Self { x: -self.x, y: -self.y, z: -self.z, w: -self.w } }
+}
+
+//	Rotation of vectors
+impl Mul<FixVec3Wide> for FixQuat {
+	type Output = FixVec3Wide;
+	#[inline] fn mul(self, v: FixVec3Wide) -> FixVec3Wide { self.rotate_wide(v) }
+}
+
+impl Mul<FixVec3> for FixQuat {
+	type Output = FixVec3;
+	fn mul(self, v: FixVec3) -> FixVec3 {
+		let r = self.rotate_wide(v.to());
+		//	Round to nearest on the way back, where `to` would round down
+		let half = FixWide::from_num(FixOrigin::DELTA) / 2;
+		FixVec3::new(r.x + half, r.y + half, r.z + half)
+	}
+}
+
+//	Implement TypeVec3 for Bevy's DVec3
+impl TypeVec3 for DVec3 {
+	type Scalar = f64;
+	
+	//	Constructors
+	#[inline] fn new<N>(x: N, y: N, z: N) -> Self 
+		where N: Cast<f64> { Self::new(x.cast(), y.cast(), z.cast()) }
+	#[inline(always)] fn zero() -> Self { Self::ZERO }
+
+	//	Access/conversions
+	#[inline(always)] fn x(&self) -> f64 { self.x }
+    #[inline(always)] fn y(&self) -> f64 { self.y }
+    #[inline(always)] fn z(&self) -> f64 { self.z }
+
+	#[inline(always)] fn to_f64(self) -> DVec3 { self }
+
+	//	Arithmetic
+	#[inline(always)] fn dot(self, other: Self) -> f64 { self.dot(other) }
+	#[inline(always)] fn cross(self, other: Self) -> Self { self.cross(other) }
+	#[inline(always)] fn mag2(self) -> f64 { self.dot(self) }
+
+	//	Metric
+	#[inline(always)] fn length(self) -> f64 { self.length() }
+	#[inline(always)] fn distance(self, other: Self) -> f64 { self.distance(other) }
+	#[inline(always)] fn normalize_or_zero(self) -> Self { self.normalize_or_zero() }
+	#[inline(always)] fn angle_between(self, other: Self) -> f64 { self.angle_between(other) }
+	//	Zero-safe like the fixed-point vectors, where glam's would divide by zero
+	#[inline] fn project_onto(self, onto: Self) -> Self {
+		let u = onto.normalize_or_zero();
+		u * self.dot(u)
+	}
+	#[inline] fn reject_from(self, from: Self) -> Self { self - TypeVec3::project_onto(self, from) }
+
+	//	Geometry
+	//	Leaves the fixed-point world: for rendering and tooling, not simulation
+	#[inline] fn rotate(self, rot: FixQuat) -> Self { rot.to_dquat() * self }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use bevy::math::EulerRot;
+	use std::f64::consts::PI;
+
+	const EPS_FIXED: FixOrigin = FixOrigin::DELTA;
+	const EPS_FP_FAR: FixOrigin = FixOrigin::ONE;
+
+	//		Tests
+	//	Construction
+	#[test] fn test_new_generic() {
+		let v_i64 = FixVec3::new(1, 2, 3);
+		let v_f64 = FixVec3::new(1., 2., 3.);
+		//let v_mix = FixVec3::new(1, 2., 3f32);
+
+		assert_eq!(v_i64, v_f64);
+        //assert_eq!(v_f64, v_mix);
+		assert_eq!(v_i64.x, FixOrigin::from_num(1));
+	}
+
+	//	Arithmetic
+	#[test] fn test_rot90() {
+		let vec = FixVec3::new(1, 0, 0);
+		let rot = FixQuat::from_dquat(DQuat::from_rotation_z(PI / 2.0));
+		let res = vec.rotate(rot);
+
+		//	Expected: (0, 1, 0)
+		assert!(res.x.abs() < EPS_FIXED, "X should be 0, got {:?}", res.x);
+		assert!((res.y - FixOrigin::ONE).abs() < EPS_FIXED, "Y should be 1, got {:?}", res.y);
+	}
+
+	//	Stability
+	#[test] fn test_far_stable() {
+		let far_dist = 1.5e13;
+
+		let far = FixVec3::new(far_dist, 0., 0.);
+		let rot = FixQuat::from_dquat(DQuat::from_rotation_z(PI / 2.0));
+		let res = far.rotate(rot);
+
+		//	Check: magnitude preserved? (its square is out of range, `mag2` would
+		//	overflow)
+		assert!((res.length() - far.length()).abs() < EPS_FP_FAR);
+
+		//	Check: correctness
+		assert!(res.x.abs() < EPS_FP_FAR);
+		assert!((res.y - FixOrigin::from_num(far_dist)).abs() < EPS_FP_FAR)
+	}
+
+	#[test] fn test_far_precise() {
+		let q = FixQuat::from_dquat(DQuat::from_rotation_z(0.12345));
+        let q_ = q.conjugate();
+
+		let start = FixVec3::new(1, 0, 0);
+        let rotated = start.rotate(q);
+        let recovered = rotated.rotate(q_);
+
+        let err = (recovered - start).mag2();
+        assert!(err < EPS_FIXED);
+	}
+
+	//	Metric ops
+	#[test] fn test_far_length() {
+		let far_dist = 1.5e13;
+
+		let far = FixVec3::new(far_dist, 0., 0.);
+		let rot = FixQuat::from_dquat(DQuat::from_rotation_z(PI / 2.0));
+		let res = far.rotate(rot);
+
+		//	Check: length preserved, though its square is out of range
+		assert_eq!(far.length(), FixOrigin::from_num(far_dist));
+		assert!((res.length() - far.length()).abs() < EPS_FP_FAR);
+
+		//	Check: exact for exact inputs
+		let a = FixVec3::new(3e13, 0., 0.);
+		let b = FixVec3::new(0., 4e13, 0.);
+		assert_eq!(a.distance(b), FixOrigin::from_num(5e13));
+		assert_eq!(FixVec3::new(1, 1, 0).length().to_bits(), 92682);
+	}
+
+	#[test] fn test_far_normalize() {
+		let far = FixVec3::new(1.5e13, -1.5e13, 0.);
+		let unit = far.normalize_or_zero();
+		let half_sqrt2 = FixOrigin::from_num(0.5f64.sqrt());
+
+		assert!((unit.x - half_sqrt2).abs() <= EPS_FIXED);
+		assert!((unit.y + half_sqrt2).abs() <= EPS_FIXED);
+		assert!((unit.length() - FixOrigin::ONE).abs() <= EPS_FIXED);
+		assert_eq!(FixVec3::ZERO.normalize_or_zero(), FixVec3::ZERO);
+
+		//	Wide vectors beyond what their length can hold
+		let huge = FixVec3Wide::new(FixWide::MAX, FixWide::MAX, FixWide::ZERO);
+		assert!((huge.normalize_or_zero().x - FixWide::from_num(0.5f64.sqrt())).abs() < FixWide::from_num(1e-15));
+	}
+
+	#[test] fn test_far_angle() {
+		let a = FixVec3::new(1.5e13, 0., 0.);
+		let b = FixVec3::new(1.5e13, 1.5e13, 0.);
+		let c = FixVec3::new(0., 0., -1.5e13);
+
+		assert!((a.angle_between(b) - FixOrigin::from_num(PI / 4.0)).abs() <= EPS_FIXED);
+		assert!((a.angle_between(c) - FixOrigin::from_num(PI / 2.0)).abs() <= EPS_FIXED);
+		assert!((a.angle_between(-a) - FixOrigin::from_num(PI)).abs() <= EPS_FIXED);
+		assert_eq!(a.angle_between(a), FixOrigin::ZERO);
+	}
+
+	#[test] fn test_far_project() {
+		let far = FixVec3::new(1.5e13, 2e13, -1e13);
+		let axis = FixVec3::new(0., 1e13, 0.);
+
+		let proj = far.project_onto(axis);
+		let rej = far.reject_from(axis);
+		assert!(proj.x.abs() < EPS_FP_FAR && proj.z.abs() < EPS_FP_FAR);
+		assert!((proj.y - FixOrigin::from_num(2e13)).abs() < EPS_FP_FAR);
+		assert!(rej.y.abs() < EPS_FP_FAR);
+		assert!((proj + rej - far).length() < EPS_FP_FAR);
+
+		assert_eq!(far.project_onto(FixVec3::ZERO), FixVec3::ZERO);
+	}
+
+	//	Overflow
+	#[test] fn test_overflow_modes() {
+		let far = FixVec3::new(1e13, 0., 0.);
+		let near = FixVec3::new(2, 0, 0);
+
+		assert_eq!(far.checked_dot(far), None);
+		assert_eq!(far.saturating_dot(far), FixOrigin::MAX);
+		assert_eq!(far.wrapping_dot(far), far.x.wrapping_mul(far.x));
+		assert_eq!(near.checked_dot(near), Some(FixOrigin::from_num(4)));
+
+		let max = FixVec3::new(FixOrigin::MAX, FixOrigin::MIN, FixOrigin::ZERO);
+		assert_eq!(max.checked_add(near), None);
+		assert_eq!(max.saturating_add(near), max);
+		assert_eq!(max.wrapping_add(near).x, FixOrigin::MIN + FixOrigin::from_num(2) - FixOrigin::DELTA);
+		assert_eq!(max.checked_neg(), None);
+		assert_eq!(max.saturating_neg(), FixVec3::new(-FixOrigin::MAX, FixOrigin::MAX, FixOrigin::ZERO));
+		assert_eq!(near.checked_div(FixOrigin::ZERO), None);
+
+		let y = FixVec3::new(0., 1e13, 0.);
+		assert_eq!(far.checked_cross(y), None);
+		assert_eq!(far.saturating_cross(y).z, FixOrigin::MAX);
+		assert_eq!(near.checked_cross(FixVec3::new(0, 3, 0)), Some(FixVec3::new(0, 0, 6)));
+	}
+
+	#[cfg(not(any(feature = "fixed-saturating", feature = "fixed-wrapping")))]
+	#[test] #[should_panic(expected = "fixed-point vector overflow")]
+	fn test_overflow_panics() {
+		let far = FixVec3::new(1e13, 0., 0.);
+		let _ = far.cross(FixVec3::new(0., 1e13, 0.));
+	}
+
+	//	Also with `fixed-wrapping` enabled alongside
+	#[cfg(feature = "fixed-saturating")]
+	#[test] fn test_overflow_saturates() {
+		let far = FixVec3::new(1e13, 0., 0.);
+		assert_eq!(far.cross(FixVec3::new(0., 1e13, 0.)).z, FixOrigin::MAX);
+	}
+
+	#[cfg(not(any(feature = "fixed-saturating", feature = "fixed-wrapping")))]
+	#[test] #[should_panic(expected = "fixed-point vector overflow")]
+	fn test_mag2_follows_policy() {
+		let _ = TypeVec3::mag2(FixVec3::new(1e13, 0., 0.));
+	}
+
+	#[test] fn test_wide_products() {
+		//	Specific angular momentum r × v at 100 AU, out of range for FixOrigin
+		let r = FixVec3::new(1.5e13, 0., 0.);
+		let v = FixVec3::new(0., 3e4, 0.);
+
+		let h = r.cross_wide(v);
+		assert_eq!(h, FixVec3Wide::new(0., 0., 4.5e17));
+		assert_eq!(r.dot_wide(FixVec3::new(2e5, 0., 0.)), FixWide::from_num(3e18));
+		assert_eq!(r.checked_cross(v), None);
+	}
+
+	//	Quaternions
+	const EPS_TRIG: f64 = 1e-5;
+
+	fn assert_quat(q: FixQuat, d: DQuat) {
+		let d = if q.to_dquat().dot(d) < 0. { -d } else { d };
+		assert!(q.to_dquat().abs_diff_eq(d, EPS_TRIG), "{:?} vs {:?}", q.to_dquat(), d);
+	}
+
+	#[test] fn test_quat_construction() {
+		let a = FixWide::from_num(0.7);
+		assert_quat(FixQuat::from_rotation_x(a), DQuat::from_rotation_x(0.7));
+		assert_quat(FixQuat::from_rotation_z(-FixWide::PI), DQuat::from_rotation_z(-PI));
+
+		let axis = FixVec3::new(1, 2, -2);
+		let q = FixQuat::from_axis_angle(axis, FixWide::from_num(2.5));
+		assert_quat(q, DQuat::from_axis_angle(DVec3::new(1., 2., -2.).normalize(), 2.5));
+
+		let (axis, angle) = q.to_axis_angle();
+		assert!((axis.to_f64() - DVec3::new(1., 2., -2.) / 3.).length() < EPS_TRIG);
+		assert!((angle.to_num::<f64>() - 2.5).abs() < EPS_TRIG);
+		assert_eq!(FixQuat::IDENTITY.to_axis_angle().1, FixWide::ZERO);
+	}
+
+	#[test] fn test_quat_euler() {
+		let (x, y, z) = (0.3, -1.1, 2.9);
+		let q = FixQuat::from_euler_xyz(FixWide::from_num(x), FixWide::from_num(y), FixWide::from_num(z));
+		assert_quat(q, DQuat::from_euler(EulerRot::XYZ, x, y, z));
+
+		let (ex, ey, ez) = q.to_euler_xyz();
+		assert!((ex.to_num::<f64>() - x).abs() < EPS_TRIG);
+		assert!((ey.to_num::<f64>() - y).abs() < EPS_TRIG);
+		assert!((ez.to_num::<f64>() - z).abs() < EPS_TRIG);
+	}
+
+	#[test] fn test_quat_product() {
+		let a = DQuat::from_euler(EulerRot::XYZ, 0.2, 0.4, -0.9);
+		let b = DQuat::from_axis_angle(DVec3::new(0., 0.6, 0.8), 1.3);
+		let (fa, fb) = (FixQuat::from_dquat(a), FixQuat::from_dquat(b));
+
+		assert_quat(fa * fb, a * b);
+		assert_quat(fa * fa.conjugate(), DQuat::IDENTITY);
+
+		//	Rotations compose like the product
+		let v = FixVec3::new(10, -20, 30);
+		let (lhs, rhs) = ((fa * fb) * v, fa * (fb * v));
+		assert!((lhs - rhs).length() <= EPS_FIXED * 2);
+		assert!((lhs.to_f64() - a * b * DVec3::new(10., -20., 30.)).length() < 1e-4);
+	}
+
+	#[test] fn test_quat_normalize() {
+		let q = FixQuat::from_dquat(DQuat::from_rotation_y(0.5));
+		let half = FixAngles::from_num(0.5);
+		let scaled = FixQuat::from_xyzw(q.x * half, q.y * half, q.z * half, q.w * half);
+
+		assert_quat(scaled.normalize(), DQuat::from_rotation_y(0.5));
+		assert!((scaled.normalize().dot(scaled.normalize()) - FixWide::ONE).abs() < FixWide::from_num(1e-17));
+		assert_eq!(FixQuat::from_xyzw(FixAngles::ZERO, FixAngles::ZERO, FixAngles::ZERO, FixAngles::ZERO).normalize(), FixQuat::IDENTITY);
+
+		//	Drift from repeated products stays bounded once renormalised
+		let step = FixQuat::from_dquat(DQuat::from_rotation_z(0.001));
+		let mut acc = FixQuat::IDENTITY;
+		for _ in 0..1000 { acc = (acc * step).normalize(); }
+		assert_quat(acc, DQuat::from_rotation_z(1.0));
+	}
+
+	#[test] fn test_quat_slerp() {
+		let a = FixQuat::from_dquat(DQuat::from_rotation_z(0.2));
+		let b = FixQuat::from_dquat(DQuat::from_rotation_z(1.4));
+		let t = |t: f64| FixWide::from_num(t);
+
+		assert_quat(a.slerp(b, t(0.)), DQuat::from_rotation_z(0.2));
+		assert_quat(a.slerp(b, t(1.)), DQuat::from_rotation_z(1.4));
+		assert_quat(a.slerp(b, t(0.25)), DQuat::from_rotation_z(0.5));
+		assert_quat(a.slerp(a, t(0.5)), DQuat::from_rotation_z(0.2));
+
+		//	Shorter arc through the double cover
+		assert_quat(a.slerp(-b, t(0.5)), DQuat::from_rotation_z(0.8));
+	}
+
+	#[test] fn test_far_rotate_deterministic() {
+		let far = FixVec3::new(1.5e13, -2e12, 3e11);
+		let q = FixQuat::from_euler_xyz(FixWide::from_num(0.1), FixWide::from_num(0.2), FixWide::from_num(0.3));
+
+		//	Round trip at 100 AU, in fixed point throughout
+		let back = q.conjugate() * (q * far);
+		assert!((back - far).length() < EPS_FP_FAR);
+		assert!(((q * far).length() - far.length()).abs() < EPS_FP_FAR);
+	}
+
+	#[test] fn test_dvec_metric() {
+		let a = DVec3::new(3., 4., 0.);
+		assert_eq!(TypeVec3::length(a), 5.);
+		assert_eq!(TypeVec3::project_onto(a, DVec3::X), DVec3::new(3., 0., 0.));
+		assert_eq!(TypeVec3::project_onto(a, DVec3::ZERO), DVec3::ZERO);
+		assert_eq!(TypeVec3::reject_from(a, DVec3::X), DVec3::new(0., 4., 0.));
+	}
+
+	//	Serialization: raw bits in binary formats, exact decimals in text ones,
+	//	and every bit back either way
+	#[cfg(feature = "serde")]
+	#[test] fn test_serde_round_trip() {
+		let v = FixVec3::new(FixOrigin::MAX, FixOrigin::from_bits(-7), FixOrigin::DELTA);
+		let w = FixVec3Wide::new(FixWide::MIN, FixWide::from_bits(1), FixWide::from_num(-1.5e13));
+		let q = FixQuat::from_dquat(DQuat::from_euler(EulerRot::XYZ, 0.1, -0.2, 0.3));
+
+		let bits = |v: FixVec3| [v.x.to_bits(), v.y.to_bits(), v.z.to_bits()];
+		let wide_bits = |v: FixVec3Wide| [v.x.to_bits(), v.y.to_bits(), v.z.to_bits()];
+		let quat_bits = |q: FixQuat| [q.x.to_bits(), q.y.to_bits(), q.z.to_bits(), q.w.to_bits()];
+
+		let bytes = bincode::serialize(&v).unwrap();
+		assert_eq!(bytes.len(), 3 * 8);
+		assert_eq!(bits(bincode::deserialize(&bytes).unwrap()), bits(v));
+		assert_eq!(wide_bits(bincode::deserialize(&bincode::serialize(&w).unwrap()).unwrap()), wide_bits(w));
+		assert_eq!(quat_bits(bincode::deserialize(&bincode::serialize(&q).unwrap()).unwrap()), quat_bits(q));
+
+		let json = serde_json::to_string(&v).unwrap();
+		assert_eq!(json, r#"{"x":"140737488355327.99998","y":"-0.0001","z":"0.00002"}"#);
+		assert_eq!(bits(serde_json::from_str(&json).unwrap()), bits(v));
+		assert_eq!(wide_bits(serde_json::from_str(&serde_json::to_string(&w).unwrap()).unwrap()), wide_bits(w));
+		assert_eq!(quat_bits(serde_json::from_str(&serde_json::to_string(&q).unwrap()).unwrap()), quat_bits(q));
+	}
+}
@@ -0,0 +1,574 @@
+//  	Imports
+use std::cmp::Ordering;
+
+use fixed::{FixedI64, FixedI128};
+use fixed::types::{I48F16, extra::U62};
+
+//		Deterministic fixed-point math
+//	Elementary functions on the raw bits of fixed-point numbers, using integer
+//	arithmetic only: the same inputs give the same bits on every target and at
+//	every optimisation level. Unlike the `fixed` crate's own `sqrt`, which
+//	truncates, roots here are rounded to the nearest representable value.
+//
+//	The transcendental functions evaluate in Q62 (i128 with 62 fractional bits)
+//	and round once into the target type. Their error is a few Q62 ulps on top of
+//	that rounding: half an ulp plus ~2^-60 in absolute terms (relative for `exp`),
+//	which the tests below pin against f64 and high-precision references. Types
+//	may have at most 62 fractional bits.
+
+//	Raw access to a fixed-point type
+pub trait FixedRaw: Copy + PartialOrd {
+	//	Fractional bits
+	const FRAC: u32;
+	const ZERO: Self;
+	const MAX: Self;
+
+	fn to_raw(self) -> i128;
+	//	None if `raw` does not fit the type
+	fn from_raw(raw: i128) -> Option<Self>;
+}
+
+macro_rules! impl_fixed_raw {
+	($T:ty, $Bits:ty) => {
+		impl FixedRaw for $T {
+			const FRAC: u32 = <$T>::FRAC_NBITS;
+			const ZERO: Self = <$T>::ZERO;
+			const MAX: Self = <$T>::MAX;
+
+			#[inline] fn to_raw(self) -> i128 { self.to_bits() as i128 }
+			#[inline] fn from_raw(raw: i128) -> Option<Self> { <$Bits>::try_from(raw).ok().map(<$T>::from_bits) }
+		}
+	};
+}
+
+impl_fixed_raw!(I48F16, i64);
+impl_fixed_raw!(FixedI64<U62>, i64);
+impl_fixed_raw!(FixedI128<U62>, i128);
+
+//		Square roots
+//	√x rounded to nearest. Panics if `x` is negative.
+pub fn sqrt<F: FixedRaw>(x: F) -> F {
+	let raw = x.to_raw();
+	assert!(raw >= 0, "square root of a negative number");
+
+	//	√(raw·2^-F) = √(raw·2^F)·2^-F
+	let root = isqrt_nearest(U256::from(raw as u128).shl(F::FRAC));
+	//	√x <= max(x, 1), so only a type that cannot hold 1 could overflow
+	F::from_raw(root as i128).expect("square root fits the type")
+}
+
+//	1/√x rounded to nearest; None for x <= 0 or if the result overflows.
+pub fn rsqrt<F: FixedRaw>(x: F) -> Option<F> {
+	let raw = x.to_raw();
+	if raw <= 0 {
+		return None;
+	}
+
+	//	2^F/√(raw·2^-F) = √(2^3F/raw). With t = ⌊√⌊2^(3F+2)/raw⌋⌋ = ⌊2√(2^3F/raw)⌋,
+	//	the nearest integer to √(2^3F/raw) is (t + 1)/2.
+	let (quot, _) = U256::ONE.shl(3 * F::FRAC + 2).div_rem(raw as u128);
+	let (t, _) = isqrt(quot);
+	i128::try_from(t.div_ceil(2)).ok().and_then(F::from_raw)
+}
+
+//	√(x² + y² + z²) rounded to nearest, without intermediate overflow; None if
+//	the result does not fit the type.
+pub fn hypot3<F: FixedRaw>(x: F, y: F, z: F) -> Option<F> {
+	//	√(Σ raw²·2^-2F) = √(Σ raw²)·2^-F
+	let sum = [x, y, z].iter()
+		.map(|c| { let a = c.to_raw().unsigned_abs(); U256::mul(a, a) })
+		.fold(U256::ZERO, U256::add);
+	let root = isqrt_nearest(sum);
+	i128::try_from(root).ok().and_then(F::from_raw)
+}
+
+//	⌊√n⌋ and the remainder n - ⌊√n⌋², two bits at a time
+fn isqrt(n: U256) -> (u128, U256) {
+	let (mut root, mut rem) = (0u128, U256::ZERO);
+	for pair in (0..128).rev() {
+		rem = rem.shl(2).add(U256::from(n.bits(2 * pair, 2)));
+		let trial = U256::from(root).shl(2).add(U256::ONE);
+		root <<= 1;
+		if rem >= trial {
+			rem = rem.sub(trial);
+			root |= 1;
+		}
+	}
+	(root, rem)
+}
+
+//	√n rounded to nearest: r + 1 when n - r² > r, as (r + ½)² = r² + r + ¼
+fn isqrt_nearest(n: U256) -> u128 {
+	let (root, rem) = isqrt(n);
+	if rem > U256::from(root) { root + 1 } else { root }
+}
+
+//		Trigonometry
+//	sin x and cos x. Any argument is reduced exactly, against 254 bits of π/2.
+pub fn sin_cos<F: FixedRaw>(x: F) -> (F, F) {
+	let raw = x.to_raw();
+	let (quadrant, r) = reduce_half_pi(raw.unsigned_abs(), F::FRAC);
+	let (s, c) = sin_cos_q62(r);
+	let (s, c) = match quadrant {
+		0 => (s, c),
+		1 => (c, -s),
+		2 => (-s, -c),
+		_ => (-c, s),
+	};
+	let s = if raw < 0 { -s } else { s };
+	let fits = "sine and cosine fit the type";
+	(from_q62(s).expect(fits), from_q62(c).expect(fits))
+}
+#[inline] pub fn sin<F: FixedRaw>(x: F) -> F { sin_cos(x).0 }
+#[inline] pub fn cos<F: FixedRaw>(x: F) -> F { sin_cos(x).1 }
+
+//	tan x; None where it overflows the type. Its absolute error is that of cos x
+//	relative to cos x, so it degrades towards odd multiples of π/2.
+pub fn tan<F: FixedRaw>(x: F) -> Option<F> {
+	let raw = x.to_raw();
+	let (quadrant, r) = reduce_half_pi(raw.unsigned_abs(), F::FRAC);
+	let (s, c) = sin_cos_q62(r);
+	let (num, den) = if quadrant % 2 == 0 { (s, c) } else { (-c, s) };
+	if den == 0 {
+		return None;
+	}
+	let t = div_round(num << Q, den);
+	from_q62(if raw < 0 { -t } else { t })
+}
+
+//	Angle of the point (x, y), taken as atan2(y, x) with y first, in [-π, π];
+//	None where that overflows the type
+pub fn atan2<F: FixedRaw>(y: F, x: F) -> Option<F> {
+	from_q62(atan2_q62(y.to_raw(), x.to_raw()))
+}
+
+//	asin x in [-π/2, π/2] and acos x in [0, π]; None outside [-1, 1] or where the
+//	angle overflows the type.
+pub fn asin<F: FixedRaw>(x: F) -> Option<F> {
+	let (s, c) = unit_sin_cos(x)?;
+	from_q62(atan2_q62(s, c))
+}
+pub fn acos<F: FixedRaw>(x: F) -> Option<F> {
+	let (s, c) = unit_sin_cos(x)?;
+	from_q62(atan2_q62(c, s))
+}
+
+//		Exponentials
+//	e^x, rounded to zero far below the type's resolution; None on overflow
+pub fn exp<F: FixedRaw>(x: F) -> Option<F> {
+	//	e^±64 is past the range and resolution of every type
+	let raw = x.to_raw();
+	if raw > 64 << F::FRAC {
+		return None;
+	}
+	if raw < -64 << F::FRAC {
+		return Some(F::ZERO);
+	}
+
+	//	x = k ln 2 + r with |r| <= ln 2 / 2, then e^x = 2^k e^r
+	let x = raw << (Q - F::FRAC);
+	let k = div_round(x, LN2);
+	let r = x - round_shr(k * LN2_Q118, 118 - Q);
+	let mut e = ONE;
+	for n in (1..=20).rev() {
+		e = ONE + mul(r, e) / n;
+	}
+
+	let shift = k + F::FRAC as i128 - Q as i128;
+	let raw = match shift {
+		0..127 => e.checked_mul(1 << shift)?,
+		127.. => return None,
+		_ => round_shr(e, shift.unsigned_abs().min(127) as u32),
+	};
+	F::from_raw(raw)
+}
+
+//	ln x; None for x <= 0 or where it overflows the type
+pub fn ln<F: FixedRaw>(x: F) -> Option<F> {
+	let raw = x.to_raw();
+	if raw <= 0 {
+		return None;
+	}
+
+	//	x = m 2^e with m in [1, 2) as Q62
+	let top = 127 - raw.leading_zeros();
+	let m = if top >= Q { round_shr(raw, top - Q) } else { raw << (Q - top) };
+	let mut e = top as i128 - F::FRAC as i128;
+
+	//	ln m = 2 atanh((m - 1)/(m + 1)), or for m > √2 the same of m/2, plus ln 2
+	let (num, den) = if m > SQRT_2 {
+		e += 1;
+		(m - 2 * ONE, m + 2 * ONE)
+	} else {
+		(m - ONE, m + ONE)
+	};
+	let s = div_round(num << Q, den);
+	let s2 = mul(s, s);
+	let mut acc = 0;
+	for n in (0..13).rev() {
+		acc = ONE / (2 * n + 1) + mul(s2, acc);
+	}
+	from_q62(2 * mul(s, acc) + round_shr(e * LN2_Q118, 118 - Q))
+}
+
+//		Q62 kernels
+const Q: u32 = 62;
+const ONE: i128 = 1 << Q;
+const FRAC_PI_4: i128 = 0x3243f6a8885a308d;
+const FRAC_PI_2: i128 = 0x6487ed5110b4611a;
+const PI: i128 = 0xc90fdaa22168c235;
+const TAN_FRAC_PI_8: i128 = 0x1a827999fcef3242;
+const SQRT_2: i128 = 0x5a827999fcef3242;
+const LN2: i128 = round_shr(LN2_Q118, 118 - Q);
+const LN2_Q118: i128 = 0x2c5c85fdf473de6af278ece600fcbe;
+//	⌊2^128 · 2/π⌋, and π/2 as a Q126 word followed by the next 128 bits
+const TWO_OVER_PI_Q128: u128 = 0xa2f9836e4e441529fc2757d1f534ddc0;
+const FRAC_PI_2_HI: u128 = 0x6487ed5110b4611a62633145c06e0e68;
+const FRAC_PI_2_LO: u128 = 0x948127044533e63a0105df531d89cd91;
+
+//	Round half up, without overflowing
+const fn round_shr(v: i128, n: u32) -> i128 {
+	match n {
+		0 => v,
+		1..127 => ((v >> (n - 1)) + 1) >> 1,
+		_ => 0,
+	}
+}
+
+//	Round half away from zero
+fn div_round(n: i128, d: i128) -> i128 {
+	let (q, r) = (n / d, n % d);
+	if r.unsigned_abs() >= d.unsigned_abs() - r.unsigned_abs() {
+		if (n < 0) == (d < 0) { q + 1 } else { q - 1 }
+	} else {
+		q
+	}
+}
+
+#[inline] fn mul(a: i128, b: i128) -> i128 { round_shr(a * b, Q) }
+
+fn from_q62<F: FixedRaw>(q: i128) -> Option<F> { F::from_raw(round_shr(q, Q - F::FRAC)) }
+
+//	a 2^-frac = k π/2 + r with |r| <= π/4 (give or take an ulp); k mod 4 and r in Q62
+fn reduce_half_pi(a: u128, frac: u32) -> (u32, i128) {
+	//	k to nearest from a 128-bit 2/π; a tie that rounds the wrong way only
+	//	leaves r a hair past π/4
+	let k = U256::mul(a, TWO_OVER_PI_Q128).add(U256::ONE.shl(127 + frac)).shr(128 + frac).lo;
+
+	//	r = a 2^-frac - k π/2 in Q126, exact but for k·2^-254
+	let x = U256::from(a).shl(126 - frac);
+	let k_half_pi = U256::mul(k, FRAC_PI_2_HI).add(U256::mul(k, FRAC_PI_2_LO).shr(128));
+	let r = if x >= k_half_pi { x.sub(k_half_pi).lo as i128 } else { -(k_half_pi.sub(x).lo as i128) };
+	((k % 4) as u32, round_shr(r, 126 - Q))
+}
+
+//	Taylor series for |r| <= π/4, to r^23/23!
+fn sin_cos_q62(r: i128) -> (i128, i128) {
+	let r2 = mul(r, r);
+	let (mut s, mut c) = (ONE, ONE);
+	for n in (1..=11).rev() {
+		s = ONE - mul(r2, s) / (2 * n * (2 * n + 1));
+		c = ONE - mul(r2, c) / ((2 * n - 1) * 2 * n);
+	}
+	(mul(r, s), c)
+}
+
+//	atan2 of raw integers, whose common scale cancels
+fn atan2_q62(y: i128, x: i128) -> i128 {
+	let (ay, ax) = (y.unsigned_abs(), x.unsigned_abs());
+	let (mut lo, mut hi) = (ay.min(ax), ay.max(ax));
+	if hi == 0 {
+		return 0;
+	}
+	//	Keep the divisor below 2^127 for `div_rem`
+	if hi >> 126 != 0 {
+		(lo, hi) = (lo >> 1, hi >> 1);
+	}
+
+	//	t = lo/hi in [0, 1], to nearest
+	let (t, rem) = U256::from(lo).shl(Q).div_rem(hi);
+	let t = t.lo as i128 + (2 * rem >= hi) as i128;
+
+	//	Past tan(π/8), atan t = π/4 + atan((t - 1)/(t + 1)); then the series to u^49
+	let (base, u) = if t > TAN_FRAC_PI_8 { (FRAC_PI_4, div_round((t - ONE) << Q, t + ONE)) } else { (0, t) };
+	let u2 = mul(u, u);
+	let mut acc = 0;
+	for n in (0..25).rev() {
+		acc = ONE / (2 * n + 1) - mul(u2, acc);
+	}
+
+	let mut a = base + mul(u, acc);
+	if ay > ax {
+		a = FRAC_PI_2 - a;
+	}
+	if x < 0 {
+		a = PI - a;
+	}
+	if y < 0 { -a } else { a }
+}
+
+//	x and √(1 - x²) in Q62, for |x| <= 1
+fn unit_sin_cos<F: FixedRaw>(x: F) -> Option<(i128, i128)> {
+	let raw = x.to_raw();
+	if raw.unsigned_abs() > 1 << F::FRAC {
+		return None;
+	}
+	let s = raw << (Q - F::FRAC);
+	let c = isqrt_nearest(U256::from((ONE * ONE - s * s) as u128)) as i128;
+	Some((s, c))
+}
+
+//		Wide integers
+//	Just enough of a 256-bit unsigned integer for the roots and reductions above
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct U256 {
+	hi: u128,
+	lo: u128,
+}
+
+impl U256 {
+	const ZERO: Self = Self { hi: 0, lo: 0 };
+	const ONE: Self = Self { hi: 0, lo: 1 };
+
+	fn mul(a: u128, b: u128) -> Self {
+		let (a1, a0) = (a >> 64, a & u64::MAX as u128);
+		let (b1, b0) = (b >> 64, b & u64::MAX as u128);
+		let (m0, m1) = (a0 * b1, a1 * b0);
+		let (lo, c0) = (a0 * b0).overflowing_add(m0 << 64);
+		let (lo, c1) = lo.overflowing_add(m1 << 64);
+		Self { hi: a1 * b1 + (m0 >> 64) + (m1 >> 64) + c0 as u128 + c1 as u128, lo }
+	}
+
+	fn add(self, b: Self) -> Self {
+		let (lo, carry) = self.lo.overflowing_add(b.lo);
+		Self { hi: self.hi + b.hi + carry as u128, lo }
+	}
+
+	fn sub(self, b: Self) -> Self {
+		let (lo, borrow) = self.lo.overflowing_sub(b.lo);
+		Self { hi: self.hi - b.hi - borrow as u128, lo }
+	}
+
+	fn shl(self, n: u32) -> Self {
+		match n {
+			0 => self,
+			1..128 => Self { hi: self.hi << n | self.lo >> (128 - n), lo: self.lo << n },
+			_ => Self { hi: self.lo << (n - 128), lo: 0 },
+		}
+	}
+
+	fn shr(self, n: u32) -> Self {
+		match n {
+			0 => self,
+			1..128 => Self { hi: self.hi >> n, lo: self.lo >> n | self.hi << (128 - n) },
+			_ => Self { hi: 0, lo: self.hi >> (n - 128) },
+		}
+	}
+
+	//	`len` bits starting at bit `at`, which must not straddle the halves
+	fn bits(self, at: u32, len: u32) -> u128 {
+		let word = if at < 128 { self.lo >> at } else { self.hi >> (at - 128) };
+		word & ((1 << len) - 1)
+	}
+
+	//	Long division by a divisor below 2^127
+	fn div_rem(self, d: u128) -> (Self, u128) {
+		let (mut quot, mut rem) = (Self::ZERO, 0u128);
+		for bit in (0..256).rev() {
+			rem = rem << 1 | self.bits(bit, 1);
+			if rem >= d {
+				rem -= d;
+				quot = quot.add(Self::ONE.shl(bit));
+			}
+		}
+		(quot, rem)
+	}
+}
+
+impl From<u128> for U256 {
+	fn from(lo: u128) -> Self { Self { hi: 0, lo } }
+}
+
+impl PartialOrd for U256 {
+	fn partial_cmp(&self, other: &Self) -> Option<Ordering> { Some(self.cmp(other)) }
+}
+
+impl Ord for U256 {
+	fn cmp(&self, other: &Self) -> Ordering { (self.hi, self.lo).cmp(&(other.hi, other.lo)) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Wide = FixedI128<U62>;
+
+	//	r is √n correctly rounded iff (2r - 1)² <= 4n <= (2r + 1)²
+	fn nearest(r: i128, four_n: i128) -> bool {
+		(2 * r - 1).pow(2) <= four_n && four_n <= (2 * r + 1).pow(2)
+	}
+
+	#[test] fn test_sqrt_exact() {
+		assert_eq!(sqrt(I48F16::from_num(4)), I48F16::from_num(2));
+		assert_eq!(sqrt(Wide::from_num(2.25)), Wide::from_num(1.5));
+		assert_eq!(sqrt(I48F16::ZERO), I48F16::ZERO);
+		assert_eq!(sqrt(FixedI64::<U62>::from_num(0.25)), FixedI64::<U62>::from_num(0.5));
+	}
+
+	#[test] fn test_sqrt_rounding() {
+		//	√2·2^16 = 92681.9, which `fixed` truncates
+		assert_eq!(sqrt(I48F16::from_num(2)).to_bits(), 92682);
+		assert_eq!(I48F16::from_num(2).sqrt().to_bits(), 92681);
+
+		let mut raw = 1i64;
+		while raw < 1 << 40 {
+			let r = sqrt(I48F16::from_bits(raw)).to_bits() as i128;
+			assert!(nearest(r, 4 * ((raw as i128) << 16)), "√{} rounded to {}", raw, r);
+			raw = raw * 3 + 1;
+		}
+	}
+
+	#[test] fn test_sqrt_range() {
+		let max = sqrt(Wide::MAX);
+		assert!((max.to_num::<f64>() - Wide::MAX.to_num::<f64>().sqrt()).abs() < 1e-9);
+		let max = sqrt(I48F16::MAX);
+		assert!((max.to_num::<f64>() - I48F16::MAX.to_num::<f64>().sqrt()).abs() < 1e-4);
+	}
+
+	#[test] fn test_rsqrt() {
+		assert_eq!(rsqrt(I48F16::from_num(4)), Some(I48F16::from_num(0.5)));
+		assert_eq!(rsqrt(Wide::from_num(0.25)), Some(Wide::from_num(2)));
+		assert_eq!(rsqrt(I48F16::ZERO), None);
+		assert_eq!(rsqrt(I48F16::from_num(-1)), None);
+		//	1/√(2^-62) = 2^31 overflows the ±2 range
+		assert_eq!(rsqrt(FixedI64::<U62>::DELTA), None);
+
+		let mut raw = 1i64;
+		while raw < 1 << 40 {
+			let r = rsqrt(I48F16::from_bits(raw)).unwrap().to_bits() as i128;
+			//	r is √(2^48/raw) correctly rounded
+			let (lo, hi) = ((2 * r - 1).pow(2) * raw as i128, (2 * r + 1).pow(2) * raw as i128);
+			assert!(lo <= 4 << 48 && 4 << 48 <= hi, "1/√{} rounded to {}", raw, r);
+			raw = raw * 3 + 1;
+		}
+	}
+
+	#[test] fn test_hypot3() {
+		let h = |x: f64, y: f64, z: f64| hypot3(I48F16::from_num(x), I48F16::from_num(y), I48F16::from_num(z));
+		assert_eq!(h(3e13, 4e13, 0.), Some(I48F16::from_num(5e13)));
+		assert_eq!(h(-1.5e13, 0., 0.), Some(I48F16::from_num(1.5e13)));
+		assert_eq!(h(0., 0., 0.), Some(I48F16::ZERO));
+		//	Each component fits, the length does not
+		assert_eq!(hypot3(I48F16::MAX, I48F16::MAX, I48F16::ZERO), None);
+
+		let far = Wide::from_num(1e19);
+		let len = hypot3(far, far, Wide::ZERO).unwrap();
+		assert!((len.to_num::<f64>() / (1e19 * 2f64.sqrt()) - 1.).abs() < 1e-15);
+	}
+
+	//	Within `ulps` of the correctly rounded Q62 `bits`
+	fn near(got: Wide, bits: i128, ulps: i128) -> bool { (got.to_bits() - bits).abs() <= ulps }
+	//	Nearest I48F16 to an f64 reference, allowing for the reference's own error
+	fn nearest_f64(got: I48F16, f: f64) -> bool {
+		(got.to_num::<f64>() - f).abs() <= I48F16::DELTA.to_num::<f64>() / 2. + f.abs() * 1e-15
+	}
+
+	#[test] fn test_trig_reference() {
+		//	Against 400-bit references
+		let w = Wide::from_num;
+		let (s, c) = sin_cos(w(-2.5));
+		assert!(near(s, -2759965619382477057, 2) && near(c, -3694622810570160726, 2));
+		//	Reduced against π/2 to 254 bits, where f64-based reduction loses it all
+		let (s, c) = sin_cos(w(1e13));
+		assert!(near(s, -1332248275039254548, 2) && near(c, 4415060867781279494, 2));
+		assert!(near(tan(w(1.)).unwrap(), 7182275428781823715, 4));
+		assert!(near(atan2(w(1.), w(-2.)).unwrap(), 12349841720247939788, 2));
+		assert!(near(atan2(w(-3.), w(-1.)).unwrap(), -8727831991209378367, 2));
+		assert!(near(asin(w(0.5)).unwrap(), 2414673152692374281, 2));
+		assert!(near(acos(w(-0.25)).unwrap(), 8409301457848512057, 2));
+	}
+
+	#[test] fn test_exp_ln_reference() {
+		let w = Wide::from_num;
+		//	exp's error is relative: 10 Q62 ulps on e^10 is ~2^-62 of it
+		assert!(near(exp(w(10.)).unwrap(), 101579144341279236622315, 1 << 16));
+		assert!(near(exp(w(-20.)).unwrap(), 9505393342, 2));
+		assert!(near(ln(w(1e13)).unwrap(), 138044393234799574314, 2));
+		assert!(near(ln(Wide::from_bits(1 << 22)).unwrap(), -127863086452026556598, 2));
+	}
+
+	#[test] fn test_trig_sweep() {
+		//	I48F16 results are the nearest value, over the whole range; inputs stay
+		//	exact in f64
+		let mut raws = vec![];
+		let mut raw = 1i64;
+		while raw < 1 << 53 {
+			raws.push(raw);
+			raw = raw * 5 / 3 + 1;
+		}
+		raws.extend([1e13, 1.2345e14, 1.4e14].map(|x| I48F16::from_num(x).to_bits()));
+		for raw in raws {
+			for raw in [raw, -raw] {
+				let x = I48F16::from_bits(raw);
+				let f = x.to_num::<f64>();
+				let (s, c) = sin_cos(x);
+				assert!(nearest_f64(s, f.sin()) && nearest_f64(c, f.cos()), "sin_cos {}", f);
+				let a = atan2(x, I48F16::from_num(-3));
+				assert!(nearest_f64(a.unwrap(), f.atan2(-3.)), "atan2 {}", f);
+			}
+		}
+
+		let mut x = -1.;
+		while x <= 1. {
+			let v = I48F16::from_num(x);
+			let f = v.to_num::<f64>();
+			assert!(nearest_f64(asin(v).unwrap(), f.asin()) && nearest_f64(acos(v).unwrap(), f.acos()), "{}", f);
+			x += 0.0625 / 3.;
+		}
+	}
+
+	#[test] fn test_exp_ln_sweep() {
+		let mut x = -40.;
+		while x < 32. {
+			let v = I48F16::from_num(x);
+			let f = v.to_num::<f64>();
+			assert!(nearest_f64(exp(v).unwrap(), f.exp()), "exp {}", f);
+			x += 0.37;
+		}
+
+		let mut raw = 1i64;
+		while raw < 1 << 62 {
+			let v = I48F16::from_bits(raw);
+			assert!(nearest_f64(ln(v).unwrap(), v.to_num::<f64>().ln()), "ln {}", raw);
+			raw += raw / 4 * 3 + 1;
+		}
+	}
+
+	#[test] fn test_domains() {
+		type Unit = FixedI64<U62>;
+		assert_eq!(exp(I48F16::from_num(33)), None);
+		assert_eq!(exp(I48F16::from_num(-12)), Some(I48F16::ZERO));
+		assert_eq!(exp(Wide::from_num(-1000)), Some(Wide::ZERO));
+		assert_eq!(ln(I48F16::ZERO), None);
+		assert_eq!(ln(I48F16::from_num(-1)), None);
+		assert_eq!(asin(I48F16::from_num(1.5)), None);
+		assert_eq!(asin(I48F16::ONE), Some(I48F16::from_bits(102944)));
+		assert_eq!(atan2(I48F16::ZERO, I48F16::ZERO), Some(I48F16::ZERO));
+		//	Past the ±2 range of unit types
+		assert_eq!(atan2(Unit::ZERO, -Unit::ONE), None);
+		assert_eq!(acos(-Unit::ONE), None);
+		assert_eq!(tan(Unit::from_num(1.5)), None);
+		assert!(tan(I48F16::from_num(1.5)).is_some());
+	}
+
+	#[test] fn test_determinism() {
+		//	Pinned bits: any change here breaks lockstep replays
+		assert_eq!(sin(I48F16::ONE).to_bits(), 55147);
+		assert_eq!(atan2(I48F16::ONE, I48F16::ONE).unwrap().to_bits(), 51472);
+		let half = Wide::from_num(0.5);
+		assert_eq!(sin(half).to_bits(), 2210960053258022889);
+		assert_eq!(exp(half).unwrap().to_bits(), 7603384832371617574);
+		assert_eq!(ln(half).unwrap().to_bits(), -3196577161300663915);
+		assert_eq!(atan2(half, Wide::from_num(0.7)).unwrap().to_bits(), 2860395882443752034);
+	}
+}
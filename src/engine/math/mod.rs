@@ -0,0 +1,25 @@
+pub mod scalar;
+pub mod fixmath;
+pub mod fixed;
+pub mod pga3d_float;
+pub mod pga3d_types;
+pub mod pga3d_interp;
+pub mod pga3d_sandwich;
+pub mod pga3d_query;
+pub mod pga3d_batch;
+pub mod pga3d_bevy;
+pub mod pga3d_simd;
+pub mod pga2d;
+#[cfg(feature = "serde")]
+pub mod pga3d_serde;
+
+//  Re-exports
+pub use scalar::{Scalar, Real, Widen};
+pub use self::fixed::{TypeVec3, FixVec3, FixVec3Wide, FixQuat, FixOrigin, FixWide, FixAngles};
+pub use pga3d_float::PGA3D;
+pub use pga3d_types::{GradeError, Plane, Line, Point, Motor, Rotor, Translator};
+pub use pga3d_interp::MotorSpline;
+pub use pga3d_sandwich::Sandwich;
+pub use pga3d_query::{Intersection, Distance, Angle, Project, Intersect};
+pub use pga3d_batch::{MotorMatrix, PointSoa};
+pub use pga2d::PGA2D;
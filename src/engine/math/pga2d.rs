@@ -0,0 +1,433 @@
+#![allow(non_upper_case_globals)]
+#![allow(non_snake_case)]
+#![allow(clippy::needless_arbitrary_self_type)]
+
+//  	Imports
+use std::fmt;
+use std::ops::{Index, IndexMut, Neg, Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, BitAnd, BitOr, BitXor, Not};
+
+use bevy::reflect::Reflect;
+use fixed::FixedI128;
+use fixed::types::{I48F16, extra::U62};
+
+use super::scalar::Real;
+use super::pga3d_sandwich::Sandwich;
+use super::pga3d_types::{self as pga3d, GradeError, define_pga_type};
+
+//		PGA2D
+//	R(2,0,1), the projective plane, in the bivector.net layout. Lines are vectors
+//	(ax + by + c = 0 is a e1 + b e2 + c e0), points are bivectors (x e20 + y e01 + w e12)
+//	and motors the even subalgebra. Operators follow `PGA3D`: `*` geometric product,
+//	`^` meet, `&` join, `|` inner product and `!` dual.
+pub(crate) const basis: &[&str] = &["1", "e0", "e1", "e2", "e01", "e20", "e12", "e012"];
+pub(crate) const basis_count: usize = basis.len();
+
+#[derive(Default, Debug, Clone, Copy, PartialEq)]
+pub struct PGA2D<S: Real> {
+	mvec: [S; basis_count]
+}
+
+impl<S: Real> PGA2D<S> {
+	pub const fn zero() -> Self { Self { mvec: [S::ZERO; basis_count] } }
+
+	pub const fn new(f: S, idx: usize) -> Self {
+		let mut ret = Self::zero();
+		ret.mvec[idx] = f;
+		ret
+	}
+
+	//	Basis blades
+	pub const e0: Self = Self::new(S::ONE, 1);
+	pub const e1: Self = Self::new(S::ONE, 2);
+	pub const e2: Self = Self::new(S::ONE, 3);
+	pub const e01: Self = Self::new(S::ONE, 4);
+	pub const e20: Self = Self::new(S::ONE, 5);
+	pub const e12: Self = Self::new(S::ONE, 6);
+	pub const e012: Self = Self::new(S::ONE, 7);
+}
+
+impl<S: Real> Index<usize> for PGA2D<S> {
+	type Output = S;
+	#[inline] fn index(&self, index: usize) -> &S { &self.mvec[index] }
+}
+
+impl<S: Real> IndexMut<usize> for PGA2D<S> {
+	#[inline] fn index_mut(&mut self, index: usize) -> &mut S { &mut self.mvec[index] }
+}
+
+impl<S: Real> fmt::Display for PGA2D<S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		let terms: Vec<String> = self.mvec.iter().enumerate()
+			.filter(|(_, coeff)| coeff.abs() > S::EPS)
+			.map(|(i, coeff)| format!("{}{}",
+				format!("{:.*}", 7, coeff).trim_end_matches('0').trim_end_matches('.'),
+				if i > 0 { basis[i] } else { "" }
+			))
+			.collect();
+		if terms.is_empty() { write!(f, "0") } else { write!(f, "{}", terms.join(" + ")) }
+	}
+}
+
+//		Products
+//	Reverse, Dual, Conjugate, Involute, the geometric product and Wedge, Vee and Dot,
+//	generated by build.rs from the PGA2D signature.
+include!(concat!(env!("OUT_DIR"), "/pga2d_ops.rs"));
+
+impl<S: Real> Mul for PGA2D<S> {
+	type Output = Self;
+	#[inline] fn mul(self, b: Self) -> Self { self.geometric(b) }
+}
+
+impl<S: Real> BitXor for PGA2D<S> {
+	type Output = Self;
+	#[inline] fn bitxor(self, b: Self) -> Self { self.wedge(b) }
+}
+
+impl<S: Real> BitAnd for PGA2D<S> {
+	type Output = Self;
+	#[inline] fn bitand(self, b: Self) -> Self { self.vee(b) }
+}
+
+impl<S: Real> BitOr for PGA2D<S> {
+	type Output = Self;
+	#[inline] fn bitor(self, b: Self) -> Self { self.dot(b) }
+}
+
+//		Linear operations
+impl<S: Real> Neg for PGA2D<S> {
+	type Output = Self;
+	#[inline] fn neg(self) -> Self { Self { mvec: self.mvec.map(|x| -x) } }
+}
+
+impl<S: Real> Add for PGA2D<S> {
+	type Output = Self;
+	#[inline] fn add(self, b: Self) -> Self { Self { mvec: std::array::from_fn(|i| self[i] + b[i]) } }
+}
+
+impl<S: Real> Sub for PGA2D<S> {
+	type Output = Self;
+	#[inline] fn sub(self, b: Self) -> Self { Self { mvec: std::array::from_fn(|i| self[i] - b[i]) } }
+}
+
+impl<S: Real> Mul<S> for PGA2D<S> {
+	type Output = Self;
+	#[inline] fn mul(self, b: S) -> Self { Self { mvec: self.mvec.map(|x| x * b) } }
+}
+
+impl<S: Real> Add<S> for PGA2D<S> {
+	type Output = Self;
+	#[inline] fn add(mut self, b: S) -> Self { self[0] += b; self }
+}
+
+impl<S: Real> Sub<S> for PGA2D<S> {
+	type Output = Self;
+	#[inline] fn sub(mut self, b: S) -> Self { self[0] -= b; self }
+}
+
+//	Compound assignment, as for `PGA3D`
+macro_rules! impl_assign_ops {
+	($(($Trait:ident, $method:ident, $op:tt, $Rhs:ty)),*) => { $(
+		impl<S: Real> $Trait<$Rhs> for PGA2D<S> {
+			#[inline] fn $method(&mut self, b: $Rhs) { *self = *self $op b; }
+		}
+	)* };
+}
+
+impl_assign_ops!(
+	(AddAssign, add_assign, +, Self), (SubAssign, sub_assign, -, Self), (MulAssign, mul_assign, *, Self),
+	(AddAssign, add_assign, +, S), (SubAssign, sub_assign, -, S), (MulAssign, mul_assign, *, S)
+);
+
+//	Scalar on the left, stamped out per concrete type (orphan rules)
+macro_rules! impl_scalar_lhs_ops {
+	($($T:ty),*) => { $(
+		impl Mul<PGA2D<$T>> for $T {
+			type Output = PGA2D<$T>;
+			#[inline] fn mul(self, b: PGA2D<$T>) -> PGA2D<$T> { b * self }
+		}
+
+		impl Add<PGA2D<$T>> for $T {
+			type Output = PGA2D<$T>;
+			#[inline] fn add(self, b: PGA2D<$T>) -> PGA2D<$T> { b + self }
+		}
+
+		impl Sub<PGA2D<$T>> for $T {
+			type Output = PGA2D<$T>;
+			#[inline] fn sub(self, b: PGA2D<$T>) -> PGA2D<$T> { -b + self }
+		}
+	)* };
+}
+
+impl_scalar_lhs_ops!(f32, f64, I48F16, FixedI128<U62>);
+
+//		Norms and constructors
+impl<S: Real> PGA2D<S> {
+	pub fn norm(self: Self) -> S { (self * self.Conjugate())[0].abs().sqrt() }
+
+	pub fn inorm(self: Self) -> S { self.Dual().norm() }
+
+	pub fn normalized(self: Self) -> Self { self * (S::ONE / self.norm()) }
+
+	//	The line ax + by + c = 0
+	pub fn line(a: S, b: S, c: S) -> Self { Self::e1 * a + Self::e2 * b + Self::e0 * c }
+
+	//	The point (x, y), with an e12 weight of one
+	pub fn point(x: S, y: S) -> Self { Self::e12 + Self::e20 * x + Self::e01 * y }
+}
+
+//		Strongly typed PGA2D elements
+//	Generated like their PGA3D counterparts, see `pga3d_types`.
+//
+//	Line       = grade 1            (e0, e1, e2)
+//	Point      = grade 2            (e01, e20, e12)
+//	Motor      = even subalgebra    (1, e01, e20, e12)
+//	Rotor      = turn about origin  (1, e12)
+//	Translator = ideal even part    (1, e01, e20)
+include!(concat!(env!("OUT_DIR"), "/pga2d_elements.rs"));
+
+//		Constructors
+impl<S: Real> Line<S> {
+	//	Homogeneous equation ax + by + c = 0
+	#[inline] pub fn from_equation(a: S, b: S, c: S) -> Self { Self::new(c, a, b) }
+
+	#[inline] pub fn norm(self) -> S { (self.e1 * self.e1 + self.e2 * self.e2).sqrt() }
+	#[inline] pub fn normalized(self) -> Self { self / self.norm() }
+}
+
+impl<S: Real> Point<S> {
+	//	Euclidean point, e12 weight of one
+	#[inline] pub fn at(x: S, y: S) -> Self { Self::new(y, x, S::ONE) }
+
+	//	Ideal point (direction), no e12 weight
+	#[inline] pub fn direction(x: S, y: S) -> Self { Self::new(y, x, S::ZERO) }
+
+	#[inline] pub fn norm(self) -> S { self.e12.abs() }
+
+	//	Scaled to an e12 weight of exactly one
+	#[inline] pub fn normalized(self) -> Self { self / self.e12 }
+}
+
+impl<S: Real> Rotor<S> {
+	pub const IDENTITY: Self = Self { s: S::ONE, e12: S::ZERO };
+
+	//	Counter-clockwise rotation by `angle` about the origin
+	pub fn from_angle(angle: S) -> Self {
+		let (sin, cos) = (angle / S::TWO).sin_cos();
+		Self::new(cos, -sin)
+	}
+
+	#[inline] pub fn reverse(self) -> Self { Self::new(self.s, -self.e12) }
+}
+
+impl<S: Real> Translator<S> {
+	pub const IDENTITY: Self = Self { s: S::ONE, e01: S::ZERO, e20: S::ZERO };
+
+	//	Translation by the offset (x, y)
+	pub fn from_offset(x: S, y: S) -> Self {
+		let h = S::ONE / S::TWO;
+		Self::new(S::ONE, -x * h, y * h)
+	}
+
+	#[inline] pub fn reverse(self) -> Self { Self::new(self.s, -self.e01, -self.e20) }
+}
+
+impl<S: Real> Motor<S> {
+	pub const IDENTITY: Self = Self { s: S::ONE, e01: S::ZERO, e20: S::ZERO, e12: S::ZERO };
+
+	#[inline] pub fn reverse(self) -> Self { Self::new(self.s, -self.e01, -self.e20, -self.e12) }
+
+	#[inline] pub fn norm(self) -> S { (self.s * self.s + self.e12 * self.e12).sqrt() }
+	#[inline] pub fn normalized(self) -> Self { self / self.norm() }
+}
+
+//		Widening conversions
+impl<S: Real> From<Rotor<S>> for Motor<S> {
+	#[inline] fn from(r: Rotor<S>) -> Self { Self::new(r.s, S::ZERO, S::ZERO, r.e12) }
+}
+
+impl<S: Real> From<Translator<S>> for Motor<S> {
+	#[inline] fn from(t: Translator<S>) -> Self { Self::new(t.s, t.e01, t.e20, S::ZERO) }
+}
+
+//		Embedding into PGA3D
+//	The plane is z = 0 with the same x and y axes. The lift is the algebra
+//	homomorphism e0, e1, e2 -> e0, e1, e2 (so e20 -> -e02), which is why a lifted
+//	motor moves the embedded points and lines exactly as the 2D motor moves the
+//	originals. Points and lines meet the z = 0 plane on the way in.
+impl<S: Real> From<Point<S>> for pga3d::Point<S> {
+	#[inline] fn from(p: Point<S>) -> Self { Self::new(S::ZERO, p.e01, p.e20, p.e12) }
+}
+
+impl<S: Real> From<Line<S>> for pga3d::Line<S> {
+	//	(a e1 + b e2 + c e0) ^ e3
+	#[inline] fn from(l: Line<S>) -> Self { Self::new(S::ZERO, S::ZERO, l.e0, S::ZERO, -l.e1, l.e2) }
+}
+
+impl<S: Real> From<Motor<S>> for pga3d::Motor<S> {
+	#[inline] fn from(m: Motor<S>) -> Self {
+		Self::new(m.s, m.e01, -m.e20, S::ZERO, m.e12, S::ZERO, S::ZERO, S::ZERO)
+	}
+}
+
+//	Through a plane motor: `frame` carries z = 0 (and its axes) onto the plane the
+//	2D result lives in, e.g. an orbit's perifocal frame.
+impl<S: Real> Point<S> {
+	#[inline] pub fn embed(self, frame: &pga3d::Motor<S>) -> pga3d::Point<S> { frame.apply(self.into()) }
+}
+
+impl<S: Real> Line<S> {
+	#[inline] pub fn embed(self, frame: &pga3d::Motor<S>) -> pga3d::Line<S> { frame.apply(self.into()) }
+}
+
+impl<S: Real> Motor<S> {
+	#[inline] pub fn embed(self, frame: &pga3d::Motor<S>) -> pga3d::Motor<S> {
+		*frame * pga3d::Motor::from(self) * frame.reverse()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use super::super::pga3d_float::PGA3D;
+
+	const EPS: f64 = 1e-12;
+
+	//	Fill every field with a distinct, non-trivial value
+	trait Sample { fn sample(seed: f64) -> Self; }
+	macro_rules! impl_sample {
+		($($Name:ident),*) => { $(
+			impl Sample for $Name<f64> {
+				fn sample(seed: f64) -> Self {
+					let mut mv = PGA2D::zero();
+					for (i, &idx) in Self::BLADES.iter().enumerate() {
+						mv[idx] = ((i as f64 + 1.0) * seed).sin();
+					}
+					Self::try_from(mv).unwrap()
+				}
+			}
+		)* };
+	}
+	impl_sample!(Line, Point, Motor, Rotor, Translator);
+
+	fn assert_close(got: PGA2D<f64>, expect: PGA2D<f64>) {
+		for i in 0..basis_count {
+			assert!((got[i] - expect[i]).abs() < EPS, "blade {} differs: {} vs {}", basis[i], got, expect);
+		}
+	}
+
+	fn assert_close3(got: PGA3D<f64>, expect: PGA3D<f64>) {
+		for i in 0..16 {
+			assert!((got[i] - expect[i]).abs() < 1e-9, "blade {} differs: {} vs {}", i, got, expect);
+		}
+	}
+
+	//	Every typed product must agree with the full multivector product
+	macro_rules! check_product {
+		($op:tt, $A:ident, $B:ident) => {{
+			let (a, b) = ($A::<f64>::sample(0.7), $B::<f64>::sample(1.3));
+			assert_close(PGA2D::from(a $op b), PGA2D::from(a) $op PGA2D::from(b));
+		}};
+		($op:tt, $A:ident, $B:ident => scalar) => {{
+			let (a, b) = ($A::<f64>::sample(0.7), $B::<f64>::sample(1.3));
+			assert_close(PGA2D::new(a $op b, 0), PGA2D::from(a) $op PGA2D::from(b));
+		}};
+	}
+
+	macro_rules! check_sandwich {
+		($V:ident, $X:ident) => {{
+			let (v, x) = ($V::<f64>::sample(0.7), $X::<f64>::sample(1.3));
+			let full = PGA2D::from(v) * PGA2D::from(x) * PGA2D::from(v).Reverse();
+			assert_close(PGA2D::from(v.apply(x)), full);
+		}};
+	}
+
+	//		Tests
+	//	Products
+	#[test] fn test_products() {
+		check_product!(*, Motor, Motor);
+		check_product!(*, Motor, Rotor);
+		check_product!(*, Rotor, Motor);
+		check_product!(*, Motor, Translator);
+		check_product!(*, Translator, Motor);
+		check_product!(*, Rotor, Rotor);
+		check_product!(*, Translator, Translator);
+		check_product!(*, Rotor, Translator);
+		check_product!(*, Translator, Rotor);
+		check_product!(*, Line, Line);
+		check_product!(*, Point, Point);
+		check_product!(^, Line, Line);
+		check_product!(&, Point, Point);
+		check_product!(|, Line, Line => scalar);
+		check_product!(|, Point, Point => scalar);
+		check_product!(|, Point, Line);
+		check_product!(|, Line, Point);
+	}
+
+	#[test] fn test_sandwich() {
+		check_sandwich!(Motor, Point);
+		check_sandwich!(Motor, Line);
+		check_sandwich!(Rotor, Point);
+		check_sandwich!(Rotor, Line);
+		check_sandwich!(Translator, Point);
+		check_sandwich!(Translator, Line);
+	}
+
+	#[test] fn test_identities() {
+		let (a, b) = (PGA2D::<f64>::point(1.0, 2.0) + PGA2D::e0, PGA2D::line(3.0, -1.0, 0.5) + 2.0);
+		assert_eq!(PGA2D::<f64>::e012 & a, a);
+		assert_eq!(!!b, b);
+		assert_close((a * b).Reverse(), b.Reverse() * a.Reverse());
+		assert_eq!(PGA2D::<f64>::e20, PGA2D::e2 * PGA2D::e0);
+		assert_eq!(2.0 - a, -(a - 2.0));
+	}
+
+	//	Geometry
+	#[test] fn test_meet_and_join() {
+		let (p, q) = (Point::<f64>::at(1.0, 1.0), Point::at(3.0, 2.0));
+		let l = p & q;
+		for x in [p, q] {
+			assert!((PGA2D::from(l) ^ PGA2D::from(x))[7].abs() < EPS);
+		}
+
+		//	x = 2 meets y = 0 at (2, 0)
+		let x = Line::<f64>::from_equation(1.0, 0.0, -2.0) ^ Line::from_equation(0.0, 1.0, 0.0);
+		assert!((x.normalized().e20 - 2.0).abs() < EPS && x.normalized().e01.abs() < EPS);
+	}
+
+	#[test] fn test_rotor_and_translator() {
+		let r = Rotor::<f64>::from_angle(std::f64::consts::FRAC_PI_2);
+		let p = r.apply(Point::at(1.0, 0.0));
+		assert!(p.e20.abs() < EPS && (p.e01 - 1.0).abs() < EPS);
+
+		let t = Translator::<f64>::from_offset(2.0, -3.0);
+		assert_eq!(t.apply(Point::at(1.0, 1.0)), Point::at(3.0, -2.0));
+		assert_eq!(Motor::from(t) * Motor::from(t.reverse()), Motor::IDENTITY);
+
+		type F = I48F16;
+		let r = Rotor::<F>::from_angle(F::PI / F::TWO);
+		let p = r.apply(Point::at(F::ONE, F::ZERO));
+		assert!(p.e20.abs() < F::lit("0.001") && (p.e01 - F::ONE).abs() < F::lit("0.001"));
+	}
+
+	//	Embedding
+	#[test] fn test_embed() {
+		use super::super::pga3d_types::Rotor as Rotor3;
+		use super::super::pga3d_types::Translator as Translator3;
+
+		let frame: pga3d::Motor<f64> = Translator3::from_offset(7.0, -1.0, 2.0) * Rotor3::from_axis_angle(0.6, 0.0, 0.8, 0.9);
+		let m = Motor::from(Translator::<f64>::from_offset(0.5, 2.0)) * Motor::from(Rotor::from_angle(1.1));
+		let (p, l) = (Point::<f64>::at(1.5, -0.5), Line::from_equation(1.0, 2.0, -0.5));
+
+		//	In the reference plane
+		assert_eq!(pga3d::Point::from(p), pga3d::Point::at(1.5, -0.5, 0.0));
+		assert_close3(PGA3D::from(pga3d::Line::from(l)), PGA3D::from(pga3d::Plane::from_equation(1.0, 2.0, 0.0, -0.5)) ^ PGA3D::e3);
+
+		//	Moving in 2D, then embedding, is embedding, then moving the lifted motor
+		assert_close3(PGA3D::from(m.apply(p).embed(&frame)), PGA3D::from(m.embed(&frame).apply(p.embed(&frame))));
+		assert_close3(PGA3D::from(m.apply(l).embed(&frame)), PGA3D::from(m.embed(&frame).apply(l.embed(&frame))));
+
+		//	Incidence survives: the point on the line stays on it
+		let on = Point::<f64>::at(0.5, 0.0);
+		assert!((PGA3D::from(on.embed(&frame)) & PGA3D::from(l.embed(&frame))).norm() < 1e-9);
+	}
+}
@@ -0,0 +1,270 @@
+//  	Imports
+use super::scalar::Real;
+use super::pga3d_types::{Plane, Point, Motor};
+
+//		Batch transforms
+//	A motor's sandwich is linear in the element it acts on, and every coefficient
+//	is a quadratic form of the motor alone. Batches bake those coefficients once
+//	into a `MotorMatrix` and then pay a 3x4 matrix per element instead of the
+//	full product. The coefficients and the order of the sums are the ones of
+//	`Sandwich::apply`, so floating-point results agree with it exactly. Fixed-point
+//	ones can differ in the last bit: the matrix folds the sandwich's subtractions
+//	into negative coefficients, and fixed multiplication rounds towards -inf.
+
+//	The sandwich of a motor m as matrices, in (x, y, z) = (e032, e013, e021) for
+//	points and (e1, e2, e3) for planes.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MotorMatrix<S: Real> {
+	//	Rotation, shared by points, directions and plane normals
+	pub rot: [[S; 3]; 3],
+	//	Point translation column, scaled by the point's weight
+	pub point: [S; 3],
+	//	Plane offset row, acting on the plane normal
+	pub plane: [S; 3],
+	//	m ~m; one for unit motors
+	pub weight: S,
+}
+
+impl<S: Real> Motor<S> {
+	pub fn to_matrix(&self) -> MotorMatrix<S> {
+		let m = self;
+		MotorMatrix {
+			rot: [
+				[(m.s*m.s - m.e12*m.e12 - m.e31*m.e31 + m.e23*m.e23), S::TWO*(m.s*m.e12 + m.e31*m.e23), -S::TWO*(m.s*m.e31 - m.e12*m.e23)],
+				[-S::TWO*(m.s*m.e12 - m.e31*m.e23), (m.s*m.s - m.e12*m.e12 + m.e31*m.e31 - m.e23*m.e23), S::TWO*(m.s*m.e23 + m.e12*m.e31)],
+				[S::TWO*(m.s*m.e31 + m.e12*m.e23), -S::TWO*(m.s*m.e23 - m.e12*m.e31), (m.s*m.s + m.e12*m.e12 - m.e31*m.e31 - m.e23*m.e23)],
+			],
+			point: [
+				-S::TWO*(m.s*m.e01 + m.e02*m.e12 - m.e03*m.e31 + m.e23*m.e0123),
+				-S::TWO*(m.s*m.e02 - m.e01*m.e12 + m.e03*m.e23 + m.e31*m.e0123),
+				-S::TWO*(m.s*m.e03 + m.e01*m.e31 - m.e02*m.e23 + m.e12*m.e0123),
+			],
+			plane: [
+				S::TWO*(m.s*m.e01 - m.e02*m.e12 + m.e03*m.e31 + m.e23*m.e0123),
+				S::TWO*(m.s*m.e02 + m.e01*m.e12 - m.e03*m.e23 + m.e31*m.e0123),
+				S::TWO*(m.s*m.e03 - m.e01*m.e31 + m.e02*m.e23 + m.e12*m.e0123),
+			],
+			weight: (m.s*m.s + m.e12*m.e12 + m.e31*m.e31 + m.e23*m.e23),
+		}
+	}
+}
+
+impl<S: Real> MotorMatrix<S> {
+	//	Rotated (x, y, z), summed in the order of the sandwich formulas
+	#[inline(always)] fn rotate(&self, x: S, y: S, z: S) -> [S; 3] {
+		let r = &self.rot;
+		[
+			r[0][2]*z + r[0][1]*y + r[0][0]*x,
+			r[1][2]*z + r[1][1]*y + r[1][0]*x,
+			r[2][2]*z + r[2][1]*y + r[2][0]*x,
+		]
+	}
+
+	#[inline] pub fn point(&self, p: Point<S>) -> Point<S> {
+		let [x, y, z] = self.rotate(p.e032, p.e013, p.e021);
+		let t = &self.point;
+		Point::new(z + t[2]*p.e123, y + t[1]*p.e123, x + t[0]*p.e123, self.weight*p.e123)
+	}
+
+	#[inline] pub fn direction(&self, d: Point<S>) -> Point<S> {
+		let [x, y, z] = self.rotate(d.e032, d.e013, d.e021);
+		Point::new(z, y, x, S::ZERO)
+	}
+
+	#[inline] pub fn plane(&self, p: Plane<S>) -> Plane<S> {
+		let r = &self.rot;
+		let o = &self.plane;
+		Plane::new(
+			self.weight*p.e0 + o[0]*p.e1 + o[1]*p.e2 + o[2]*p.e3,
+			r[0][0]*p.e1 + r[0][1]*p.e2 + r[0][2]*p.e3,
+			r[1][0]*p.e1 + r[1][1]*p.e2 + r[1][2]*p.e3,
+			r[2][0]*p.e1 + r[2][1]*p.e2 + r[2][2]*p.e3,
+		)
+	}
+}
+
+//		Structure of arrays
+//	Euclidean points (or directions) with an implied weight of one, one array per axis
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PointSoa<S: Real> {
+	pub x: Vec<S>,
+	pub y: Vec<S>,
+	pub z: Vec<S>,
+}
+
+impl<S: Real> PointSoa<S> {
+	#[inline] pub fn new() -> Self { Self { x: Vec::new(), y: Vec::new(), z: Vec::new() } }
+
+	pub fn with_capacity(n: usize) -> Self {
+		Self { x: Vec::with_capacity(n), y: Vec::with_capacity(n), z: Vec::with_capacity(n) }
+	}
+
+	#[inline] pub fn len(&self) -> usize { self.x.len() }
+	#[inline] pub fn is_empty(&self) -> bool { self.x.is_empty() }
+
+	#[inline] pub fn push(&mut self, x: S, y: S, z: S) {
+		self.x.push(x);
+		self.y.push(y);
+		self.z.push(z);
+	}
+
+	#[inline] pub fn point(&self, i: usize) -> Point<S> { Point::at(self.x[i], self.y[i], self.z[i]) }
+}
+
+impl<S: Real> FromIterator<Point<S>> for PointSoa<S> {
+	//	Points are normalised to weight one on the way in
+	fn from_iter<I: IntoIterator<Item = Point<S>>>(iter: I) -> Self {
+		let mut soa = Self::new();
+		for p in iter {
+			let p = p.normalized();
+			soa.push(p.e032, p.e013, p.e021);
+		}
+		soa
+	}
+}
+
+//		Batch API
+impl<S: Real> Motor<S> {
+	pub fn apply_points(&self, xs: &mut [Point<S>]) {
+		let m = self.to_matrix();
+		for x in xs { *x = m.point(*x); }
+	}
+
+	pub fn apply_directions(&self, xs: &mut [Point<S>]) {
+		let m = self.to_matrix();
+		for x in xs { *x = m.direction(*x); }
+	}
+
+	pub fn apply_planes(&self, xs: &mut [Plane<S>]) {
+		let m = self.to_matrix();
+		for x in xs { *x = m.plane(*x); }
+	}
+
+	//	Points keep their implied weight of one, so non-unit motors are divided out
+	pub fn apply_points_soa(&self, soa: &mut PointSoa<S>) {
+		let mut m = self.to_matrix();
+		if m.weight != S::ONE {
+			let inv = S::ONE / m.weight;
+			for row in &mut m.rot { for c in row { *c *= inv; } }
+			for c in &mut m.point { *c *= inv; }
+		}
+
+		let t = m.point;
+		for ((x, y), z) in soa.x.iter_mut().zip(&mut soa.y).zip(&mut soa.z) {
+			let [rx, ry, rz] = m.rotate(*x, *y, *z);
+			(*x, *y, *z) = (rx + t[0], ry + t[1], rz + t[2]);
+		}
+	}
+
+	pub fn apply_directions_soa(&self, soa: &mut PointSoa<S>) {
+		let m = self.to_matrix();
+		for ((x, y), z) in soa.x.iter_mut().zip(&mut soa.y).zip(&mut soa.z) {
+			[*x, *y, *z] = m.rotate(*x, *y, *z);
+		}
+	}
+
+	//	Prepend this (parent) motor to every child: child <- self * child
+	pub fn compose_all(&self, children: &mut [Motor<S>]) {
+		for c in children { *c = *self * *c; }
+	}
+
+	//	Out-of-place `compose_all`, e.g. locals to world poses
+	pub fn compose_into(&self, locals: &[Motor<S>], out: &mut [Motor<S>]) {
+		assert_eq!(locals.len(), out.len(), "compose_into: length mismatch");
+		for (o, l) in out.iter_mut().zip(locals) { *o = *self * *l; }
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use super::super::pga3d_sandwich::Sandwich;
+	use super::super::pga3d_types::{Line, Rotor};
+	use fixed::types::I48F16;
+
+	fn motor() -> Motor<f64> {
+		Motor::exp(Line::new(0.3, -1.2, 0.8, 0.4, -0.25, 0.6)) * Motor::from(Rotor::from_axis_angle(0.0, 1.0, 0.0, 0.7))
+	}
+
+	fn points() -> Vec<Point<f64>> {
+		(0..17).map(|i| {
+			let t = i as f64;
+			Point::new((t * 0.3).sin(), t - 4.0, (t * 1.7).cos() * 3.0, 1.0 + t * 0.1)
+		}).collect()
+	}
+
+	//		Tests
+	#[test] fn test_matches_sandwich() {
+		//	Non-unit on purpose; the batch path must not assume normalisation
+		let m = motor() * 1.3;
+
+		let mut ps = points();
+		m.apply_points(&mut ps);
+		for (p, q) in ps.iter().zip(points()) { assert_eq!(*p, m.apply(q)); }
+
+		let mut ds: Vec<_> = points().into_iter().map(|p| Point::direction(p.e032, p.e013, p.e021)).collect();
+		let expect: Vec<_> = ds.iter().map(|&d| m.apply_direction(d)).collect();
+		m.apply_directions(&mut ds);
+		assert_eq!(ds, expect);
+
+		let mut planes: Vec<_> = points().into_iter().map(|p| Plane::new(p.e123, p.e021, p.e013, p.e032)).collect();
+		let expect: Vec<_> = planes.iter().map(|&x| m.apply(x)).collect();
+		m.apply_planes(&mut planes);
+		assert_eq!(planes, expect);
+	}
+
+	#[test] fn test_matches_sandwich_fixed() {
+		let m = motor();
+		let m = Motor::<I48F16>::new(
+			I48F16::from_num(m.s), I48F16::from_num(m.e01), I48F16::from_num(m.e02), I48F16::from_num(m.e03),
+			I48F16::from_num(m.e12), I48F16::from_num(m.e31), I48F16::from_num(m.e23), I48F16::from_num(m.e0123),
+		);
+		let src: Vec<_> = points().into_iter().map(|p| Point::<I48F16>::new(
+			I48F16::from_num(p.e021), I48F16::from_num(p.e013), I48F16::from_num(p.e032), I48F16::from_num(p.e123),
+		)).collect();
+
+		let mut ps = src.clone();
+		m.apply_points(&mut ps);
+		for (p, q) in ps.iter().zip(src) {
+			let q = m.apply(q);
+			for (a, b) in [(p.e021, q.e021), (p.e013, q.e013), (p.e032, q.e032), (p.e123, q.e123)] {
+				assert!((a - b).abs() <= I48F16::DELTA * 2, "{} vs {}", p, q);
+			}
+		}
+	}
+
+	#[test] fn test_soa() {
+		let m = motor() * 0.8;
+		let mut soa: PointSoa<f64> = points().into_iter().collect();
+		m.apply_points_soa(&mut soa);
+
+		for (i, p) in points().into_iter().enumerate() {
+			let expect = m.apply(p).normalized();
+			let got = soa.point(i);
+			for (a, b) in [(got.e032, expect.e032), (got.e013, expect.e013), (got.e021, expect.e021)] {
+				assert!((a - b).abs() < 1e-12, "point {}: {} vs {}", i, got, expect);
+			}
+		}
+
+		let mut dirs = PointSoa::new();
+		dirs.push(1.0, 2.0, 3.0);
+		m.apply_directions_soa(&mut dirs);
+		let expect = m.apply_direction(Point::direction(1.0, 2.0, 3.0));
+		assert_eq!((dirs.x[0], dirs.y[0], dirs.z[0]), (expect.e032, expect.e013, expect.e021));
+	}
+
+	#[test] fn test_compose() {
+		let parent = motor();
+		let locals: Vec<_> = (0..5).map(|i| Motor::exp(Line::new(0.1 * i as f64, 0.0, -0.2, 0.0, 0.3, 0.05 * i as f64))).collect();
+
+		let mut world = vec![Motor::IDENTITY; locals.len()];
+		parent.compose_into(&locals, &mut world);
+		let mut in_place = locals.clone();
+		parent.compose_all(&mut in_place);
+
+		for ((w, p), l) in world.iter().zip(&in_place).zip(&locals) {
+			assert_eq!(*w, parent * *l);
+			assert_eq!(w, p);
+		}
+	}
+}
@@ -0,0 +1,177 @@
+//  	Imports
+use bevy::math::{DAffine3, DQuat, DVec3, DVec4};
+use bevy::prelude::{GlobalTransform, Transform};
+
+use super::scalar::Real;
+use super::pga3d_sandwich::Sandwich;
+use super::pga3d_types::{Plane, Point, Motor, Rotor, Translator};
+
+//		Bevy interop
+//	Bevy is right-handed, Y-up, and uses the same handedness as `Rotor::from_axis_angle`,
+//	so a quaternion maps onto a rotor component by component:
+//	s = w, e23 = -x, e31 = -y, e12 = -z.
+//	Transforms carry no scale in PGA; it is dropped going in and set to one coming out.
+
+//	Points
+//	Ideal points (directions) have no weight to divide by and convert as-is.
+impl<S: Real> From<DVec3> for Point<S> {
+	#[inline] fn from(v: DVec3) -> Self { Self::at(S::from_f64(v.x), S::from_f64(v.y), S::from_f64(v.z)) }
+}
+
+impl<S: Real> From<Point<S>> for DVec3 {
+	#[inline] fn from(p: Point<S>) -> Self {
+		let v = DVec3::new(p.e032.to_f64(), p.e013.to_f64(), p.e021.to_f64());
+		if p.e123 == S::ZERO { v } else { v / p.e123.to_f64() }
+	}
+}
+
+//	Planes, as the equation coefficients (a, b, c, d) of ax + by + cz + d = 0
+impl<S: Real> From<DVec4> for Plane<S> {
+	#[inline] fn from(v: DVec4) -> Self {
+		Self::from_equation(S::from_f64(v.x), S::from_f64(v.y), S::from_f64(v.z), S::from_f64(v.w))
+	}
+}
+
+impl<S: Real> From<Plane<S>> for DVec4 {
+	#[inline] fn from(p: Plane<S>) -> Self { DVec4::new(p.e1.to_f64(), p.e2.to_f64(), p.e3.to_f64(), p.e0.to_f64()) }
+}
+
+//	Rotations
+impl<S: Real> From<DQuat> for Rotor<S> {
+	#[inline] fn from(q: DQuat) -> Self {
+		Self::new(S::from_f64(q.w), S::from_f64(-q.z), S::from_f64(-q.y), S::from_f64(-q.x))
+	}
+}
+
+impl<S: Real> From<Rotor<S>> for DQuat {
+	#[inline] fn from(r: Rotor<S>) -> Self {
+		DQuat::from_xyzw(-r.e23.to_f64(), -r.e31.to_f64(), -r.e12.to_f64(), r.s.to_f64())
+	}
+}
+
+impl<S: Real> From<DQuat> for Motor<S> {
+	#[inline] fn from(q: DQuat) -> Self { Rotor::from(q).into() }
+}
+
+//	Translations
+impl<S: Real> From<DVec3> for Translator<S> {
+	#[inline] fn from(v: DVec3) -> Self { Self::from_offset(S::from_f64(v.x), S::from_f64(v.y), S::from_f64(v.z)) }
+}
+
+impl<S: Real> From<Translator<S>> for DVec3 {
+	#[inline] fn from(t: Translator<S>) -> Self {
+		DVec3::new(t.e01.to_f64(), t.e02.to_f64(), t.e03.to_f64()) * (-2.0 / t.s.to_f64())
+	}
+}
+
+//	Rigid motions
+impl<S: Real> Motor<S> {
+	//	Motor that rotates by `rotation`, then translates by `translation`
+	pub fn from_rotation_translation(rotation: DQuat, translation: DVec3) -> Self {
+		Translator::from(translation) * Rotor::from(rotation)
+	}
+
+	//	Inverse of `from_rotation_translation`
+	pub fn to_rotation_translation(self) -> (DQuat, DVec3) {
+		let rotor = Rotor::new(self.s, self.e12, self.e31, self.e23);
+		(DQuat::from(rotor).normalize(), self.apply(Point::at(S::ZERO, S::ZERO, S::ZERO)).into())
+	}
+}
+
+impl<S: Real> From<DAffine3> for Motor<S> {
+	#[inline] fn from(a: DAffine3) -> Self {
+		let (_, rotation, translation) = a.to_scale_rotation_translation();
+		Self::from_rotation_translation(rotation, translation)
+	}
+}
+
+impl<S: Real> From<Motor<S>> for DAffine3 {
+	#[inline] fn from(m: Motor<S>) -> Self {
+		let (rotation, translation) = m.to_rotation_translation();
+		DAffine3::from_rotation_translation(rotation, translation)
+	}
+}
+
+impl<S: Real> From<Transform> for Motor<S> {
+	#[inline] fn from(t: Transform) -> Self {
+		Self::from_rotation_translation(t.rotation.as_dquat(), t.translation.as_dvec3())
+	}
+}
+
+impl<S: Real> From<Motor<S>> for Transform {
+	#[inline] fn from(m: Motor<S>) -> Self {
+		let (rotation, translation) = m.to_rotation_translation();
+		Transform::from_translation(translation.as_vec3()).with_rotation(rotation.as_quat())
+	}
+}
+
+impl<S: Real> From<GlobalTransform> for Motor<S> {
+	#[inline] fn from(g: GlobalTransform) -> Self { g.compute_transform().into() }
+}
+
+impl<S: Real> From<Motor<S>> for GlobalTransform {
+	#[inline] fn from(m: Motor<S>) -> Self { Transform::from(m).into() }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use fixed::types::I48F16;
+
+	const EPS: f64 = 1e-9;
+
+	fn axis() -> DVec3 { DVec3::new(0.3, -0.5, 0.8).normalize() }
+
+	//		Tests
+	//	Rotations
+	#[test] fn test_quat_matches_rotor() {
+		let q = DQuat::from_axis_angle(axis(), 0.9);
+		let (x, y, z) = (axis().x, axis().y, axis().z);
+		let r = Rotor::<f64>::from_axis_angle(x, y, z, 0.9);
+		assert!(DQuat::from(r).abs_diff_eq(q, EPS));
+		assert_eq!(Rotor::<f64>::from(q), Rotor::from(DQuat::from(Rotor::<f64>::from(q))));
+
+		let v = DVec3::new(1.0, 2.0, -3.0);
+		let p: DVec3 = r.apply(Point::from(v)).into();
+		assert!(p.abs_diff_eq(q * v, EPS));
+	}
+
+	//	Rigid motions
+	#[test] fn test_motor_matches_affine() {
+		let a = DAffine3::from_rotation_translation(DQuat::from_axis_angle(axis(), -1.3), DVec3::new(4.0, -2.0, 0.5));
+		let m = Motor::<f64>::from(a);
+
+		let v = DVec3::new(-1.0, 0.25, 7.0);
+		let p: DVec3 = m.apply(Point::from(v)).into();
+		assert!(p.abs_diff_eq(a.transform_point3(v), EPS));
+		assert!(DAffine3::from(m).abs_diff_eq(a, EPS));
+	}
+
+	#[test] fn test_transform_roundtrip() {
+		let t = Transform::from_xyz(1.0, 2.0, 3.0).with_rotation(DQuat::from_axis_angle(axis(), 0.4).as_quat());
+		let m = Motor::<I48F16>::from(t);
+		let back = Transform::from(m);
+		assert!(back.translation.abs_diff_eq(t.translation, 1e-3));
+		assert!(back.rotation.abs_diff_eq(t.rotation, 1e-3));
+
+		let g = GlobalTransform::from(m);
+		assert!(g.translation().abs_diff_eq(t.translation, 1e-3));
+	}
+
+	#[test] fn test_points_and_planes() {
+		assert_eq!(DVec3::from(Point::<f64>::new(2.0, 4.0, 6.0, 2.0)), DVec3::new(3.0, 2.0, 1.0));
+		assert_eq!(DVec3::from(Point::<f64>::direction(1.0, 0.0, 0.0)), DVec3::X);
+
+		let v = DVec4::new(2.0, 0.0, 1.0, -3.0);
+		assert_eq!(DVec4::from(Plane::<f64>::from(v)), v);
+		assert_eq!(DVec3::from(Translator::<f64>::from(DVec3::new(1.0, -2.0, 3.0))), DVec3::new(1.0, -2.0, 3.0));
+	}
+
+	//	Reflection
+	#[test] fn test_reflect() {
+		use bevy::reflect::{Reflect, TypePath};
+		let m: Box<dyn Reflect> = Box::new(Motor::<f64>::IDENTITY);
+		assert_eq!(m.reflect_type_path(), Motor::<f64>::type_path());
+		assert_eq!(m.downcast_ref::<Motor<f64>>(), Some(&Motor::IDENTITY));
+	}
+}
@@ -1,84 +1,89 @@
 //      Generated @ bivector.net
 //  Generator written by enki (64-bit floating-point only).
 //  Modified by AshenRustedArmor for use with multiple types.
+//  Products and involutions are now generated at build time, see build.rs.
 
 #![allow(unused_imports)]
 #![allow(dead_code)]
 #![allow(non_upper_case_globals)]
 #![allow(non_snake_case)]
 #![allow(non_camel_case_types)]
-#![feature(const_slice_len)]
+#![allow(clippy::needless_arbitrary_self_type)]
+#![allow(clippy::needless_lifetimes)]
+#![allow(clippy::redundant_static_lifetimes)]
+#![allow(clippy::suspicious_assignment_formatting)]
 
 use std::fmt;
-use std::ops::{Index,IndexMut,Add,Sub,Mul,BitAnd,BitOr,BitXor,Not};
+use std::iter::Sum;
+use std::ops::{Index,IndexMut,Neg,Add,Sub,Mul,BitAnd,BitOr,BitXor,Not};
+use std::ops::{AddAssign,SubAssign,MulAssign,BitAndAssign,BitOrAssign,BitXorAssign};
 
-// use std::f64::consts::PI;
-type Scalar = f64;
-const ZERO: Scalar = 0.0;
-const ONE:  Scalar = 1.0;
-const TWO:  Scalar = 2.0;
+use fixed::FixedI128;
+use fixed::types::{I48F16, extra::U62};
 
-const PI:   Scalar = 3.14159265358979323846;
-const EPS:  Scalar = 0.00001;
+use super::scalar::{Scalar, Real};
 
-const basis: &'static [&'static str] = &[ "1","e0","e1","e2","e3","e01","e02","e03","e12","e31","e23","e021","e013","e032","e123","e0123" ];
-const basis_count: usize = basis.len();
+pub(crate) const basis: &'static [&'static str] = &[ "1","e0","e1","e2","e3","e01","e02","e03","e12","e31","e23","e021","e013","e032","e123","e0123" ];
+pub(crate) const basis_count: usize = basis.len();
+pub(crate) const grades: [usize; basis_count] = [ 0,1,1,1,1,2,2,2,2,2,2,3,3,3,3,4 ];
 
 #[derive(Default,Debug,Clone,Copy,PartialEq)]
-struct PGA3D {
-    mvec: [Scalar; basis_count]
+pub struct PGA3D<S: Real> {
+    mvec: [S; basis_count]
 }
 
-impl PGA3D {
+impl<S: Real> PGA3D<S> {
     pub const fn zero() -> Self {
         Self {
-            mvec: [0.0; basis_count]
+            mvec: [S::ZERO; basis_count]
         }
     }
 
-    pub const fn new(f: Scalar, idx: usize) -> Self {
+    pub const fn new(f: S, idx: usize) -> Self {
         let mut ret = Self::zero();
         ret.mvec[idx] = f;
         ret
     }
 }
 
-// basis vectors are available as global constants.
-const e0: PGA3D     = PGA3D::new(ONE, 1);
-const e1: PGA3D     = PGA3D::new(ONE, 2);
-const e2: PGA3D     = PGA3D::new(ONE, 3);
-const e3: PGA3D     = PGA3D::new(ONE, 4);
-const e01: PGA3D    = PGA3D::new(ONE, 5);
-const e02: PGA3D    = PGA3D::new(ONE, 6);
-const e03: PGA3D    = PGA3D::new(ONE, 7);
-const e12: PGA3D    = PGA3D::new(ONE, 8);
-const e31: PGA3D    = PGA3D::new(ONE, 9);
-const e23: PGA3D    = PGA3D::new(ONE, 10);
-const e021: PGA3D   = PGA3D::new(ONE, 11);
-const e013: PGA3D   = PGA3D::new(ONE, 12);
-const e032: PGA3D   = PGA3D::new(ONE, 13);
-const e123: PGA3D   = PGA3D::new(ONE, 14);
-const e0123: PGA3D  = PGA3D::new(ONE, 15);
-
-impl Index<usize> for PGA3D {
-    type Output = Scalar;
+// basis vectors are available as associated constants.
+impl<S: Real> PGA3D<S> {
+    pub const e0: Self     = Self::new(S::ONE, 1);
+    pub const e1: Self     = Self::new(S::ONE, 2);
+    pub const e2: Self     = Self::new(S::ONE, 3);
+    pub const e3: Self     = Self::new(S::ONE, 4);
+    pub const e01: Self    = Self::new(S::ONE, 5);
+    pub const e02: Self    = Self::new(S::ONE, 6);
+    pub const e03: Self    = Self::new(S::ONE, 7);
+    pub const e12: Self    = Self::new(S::ONE, 8);
+    pub const e31: Self    = Self::new(S::ONE, 9);
+    pub const e23: Self    = Self::new(S::ONE, 10);
+    pub const e021: Self   = Self::new(S::ONE, 11);
+    pub const e013: Self   = Self::new(S::ONE, 12);
+    pub const e032: Self   = Self::new(S::ONE, 13);
+    pub const e123: Self   = Self::new(S::ONE, 14);
+    pub const e0123: Self  = Self::new(S::ONE, 15);
+}
+
+impl<S: Real> Index<usize> for PGA3D<S> {
+    type Output = S;
 
     fn index<'a>(&'a self, index: usize) -> &'a Self::Output {
         &self.mvec[index]
     }
 }
 
-impl IndexMut<usize> for PGA3D {
+impl<S: Real> IndexMut<usize> for PGA3D<S> {
     fn index_mut<'a>(&'a mut self, index: usize) -> &'a mut Self::Output {
         &mut self.mvec[index]
     }
 }
 
-impl fmt::Display for PGA3D {
+impl<S: Real> fmt::Display for PGA3D<S> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut n = 0;
         let ret = self.mvec.iter().enumerate().filter_map(|(i, &coeff)| {
-            if coeff > 0.00001 || coeff < -0.00001 {
+            if coeff.abs() > S::EPS {
                 n = 1;
                 Some(format!("{}{}", 
                         format!("{:.*}", 7, coeff).trim_end_matches('0').trim_end_matches('.'),
@@ -93,255 +98,99 @@ impl fmt::Display for PGA3D {
     }
 }
 
-// Reverse
-// Reverse the order of the basis blades.
-impl PGA3D {
-    pub fn Reverse(self: Self) -> PGA3D {
-        let mut res = PGA3D::zero();
-        let a = self;
-        res[0]=a[0];
-        res[1]=a[1];
-        res[2]=a[2];
-        res[3]=a[3];
-        res[4]=a[4];
-        res[5]=-a[5];
-        res[6]=-a[6];
-        res[7]=-a[7];
-        res[8]=-a[8];
-        res[9]=-a[9];
-        res[10]=-a[10];
-        res[11]=-a[11];
-        res[12]=-a[12];
-        res[13]=-a[13];
-        res[14]=-a[14];
-        res[15]=a[15];
-        res
-    }
-}
+// Reverse, Dual, Conjugate, Involute, and the geometric, Wedge, Vee and Dot products
+// the operators below reach through.
+// Generated by build.rs from the PGA3D signature.
+include!(concat!(env!("OUT_DIR"), "/pga3d_ops.rs"));
 
-// Dual
-// Poincare duality operator.
-impl PGA3D {
-    pub fn Dual(self: Self) -> PGA3D {
-        let mut res = PGA3D::zero();
-        let a = self;
-        res[0]=a[15];
-        res[1]=a[14];
-        res[2]=a[13];
-        res[3]=a[12];
-        res[4]=a[11];
-        res[5]=a[10];
-        res[6]=a[9];
-        res[7]=a[8];
-        res[8]=a[7];
-        res[9]=a[6];
-        res[10]=a[5];
-        res[11]=a[4];
-        res[12]=a[3];
-        res[13]=a[2];
-        res[14]=a[1];
-        res[15]=a[0];
-        res
-    }
-}
-
-impl Not for PGA3D {
-    type Output = PGA3D;
+// Neg
+// Multivector negation
+impl<S: Real> Neg for PGA3D<S> {
+    type Output = Self;
 
-    fn not(self: Self) -> PGA3D {
-        let mut res = PGA3D::zero();
+    fn neg(self: Self) -> Self {
+        let mut res = Self::zero();
         let a = self;
-        res[0]=a[15];
-        res[1]=a[14];
-        res[2]=a[13];
-        res[3]=a[12];
-        res[4]=a[11];
-        res[5]=a[10];
-        res[6]=a[9];
-        res[7]=a[8];
-        res[8]=a[7];
-        res[9]=a[6];
-        res[10]=a[5];
-        res[11]=a[4];
-        res[12]=a[3];
-        res[13]=a[2];
-        res[14]=a[1];
-        res[15]=a[0];
-        res
-    }
-}
-
-// Conjugate
-// Clifford Conjugation
-impl PGA3D {
-    pub fn Conjugate(self: Self) -> PGA3D {
-        let mut res = PGA3D::zero();
-        let a = self;
-        res[0]=a[0];
-        res[1]=-a[1];
-        res[2]=-a[2];
-        res[3]=-a[3];
-        res[4]=-a[4];
-        res[5]=-a[5];
-        res[6]=-a[6];
-        res[7]=-a[7];
-        res[8]=-a[8];
-        res[9]=-a[9];
-        res[10]=-a[10];
-        res[11]=a[11];
-        res[12]=a[12];
-        res[13]=a[13];
-        res[14]=a[14];
-        res[15]=a[15];
-        res
-    }
-}
-
-// Involute
-// Main involution
-impl PGA3D {
-    pub fn Involute(self: Self) -> PGA3D {
-        let mut res = PGA3D::zero();
-        let a = self;
-        res[0]=a[0];
-        res[1]=-a[1];
-        res[2]=-a[2];
-        res[3]=-a[3];
-        res[4]=-a[4];
-        res[5]=a[5];
-        res[6]=a[6];
-        res[7]=a[7];
-        res[8]=a[8];
-        res[9]=a[9];
-        res[10]=a[10];
-        res[11]=-a[11];
-        res[12]=-a[12];
-        res[13]=-a[13];
-        res[14]=-a[14];
-        res[15]=a[15];
+        res[0] = -a[0];
+        res[1] = -a[1];
+        res[2] = -a[2];
+        res[3] = -a[3];
+        res[4] = -a[4];
+        res[5] = -a[5];
+        res[6] = -a[6];
+        res[7] = -a[7];
+        res[8] = -a[8];
+        res[9] = -a[9];
+        res[10] = -a[10];
+        res[11] = -a[11];
+        res[12] = -a[12];
+        res[13] = -a[13];
+        res[14] = -a[14];
+        res[15] = -a[15];
         res
     }
 }
 
 // Mul
 // The geometric product.
-impl Mul for PGA3D {
-    type Output = PGA3D;
+impl<S: Real> Mul for PGA3D<S> {
+    type Output = Self;
 
-    fn mul(self: PGA3D, b: PGA3D) -> PGA3D {
-        let mut res = PGA3D::zero();
-        let a = self;
-        res[0]=b[0]*a[0]+b[2]*a[2]+b[3]*a[3]+b[4]*a[4]-b[8]*a[8]-b[9]*a[9]-b[10]*a[10]-b[14]*a[14];
-		res[1]=b[1]*a[0]+b[0]*a[1]-b[5]*a[2]-b[6]*a[3]-b[7]*a[4]+b[2]*a[5]+b[3]*a[6]+b[4]*a[7]+b[11]*a[8]+b[12]*a[9]+b[13]*a[10]+b[8]*a[11]+b[9]*a[12]+b[10]*a[13]+b[15]*a[14]-b[14]*a[15];
-		res[2]=b[2]*a[0]+b[0]*a[2]-b[8]*a[3]+b[9]*a[4]+b[3]*a[8]-b[4]*a[9]-b[14]*a[10]-b[10]*a[14];
-		res[3]=b[3]*a[0]+b[8]*a[2]+b[0]*a[3]-b[10]*a[4]-b[2]*a[8]-b[14]*a[9]+b[4]*a[10]-b[9]*a[14];
-		res[4]=b[4]*a[0]-b[9]*a[2]+b[10]*a[3]+b[0]*a[4]-b[14]*a[8]+b[2]*a[9]-b[3]*a[10]-b[8]*a[14];
-		res[5]=b[5]*a[0]+b[2]*a[1]-b[1]*a[2]-b[11]*a[3]+b[12]*a[4]+b[0]*a[5]-b[8]*a[6]+b[9]*a[7]+b[6]*a[8]-b[7]*a[9]-b[15]*a[10]-b[3]*a[11]+b[4]*a[12]+b[14]*a[13]-b[13]*a[14]-b[10]*a[15];
-		res[6]=b[6]*a[0]+b[3]*a[1]+b[11]*a[2]-b[1]*a[3]-b[13]*a[4]+b[8]*a[5]+b[0]*a[6]-b[10]*a[7]-b[5]*a[8]-b[15]*a[9]+b[7]*a[10]+b[2]*a[11]+b[14]*a[12]-b[4]*a[13]-b[12]*a[14]-b[9]*a[15];
-		res[7]=b[7]*a[0]+b[4]*a[1]-b[12]*a[2]+b[13]*a[3]-b[1]*a[4]-b[9]*a[5]+b[10]*a[6]+b[0]*a[7]-b[15]*a[8]+b[5]*a[9]-b[6]*a[10]+b[14]*a[11]-b[2]*a[12]+b[3]*a[13]-b[11]*a[14]-b[8]*a[15];
-		res[8]=b[8]*a[0]+b[3]*a[2]-b[2]*a[3]+b[14]*a[4]+b[0]*a[8]+b[10]*a[9]-b[9]*a[10]+b[4]*a[14];
-		res[9]=b[9]*a[0]-b[4]*a[2]+b[14]*a[3]+b[2]*a[4]-b[10]*a[8]+b[0]*a[9]+b[8]*a[10]+b[3]*a[14];
-		res[10]=b[10]*a[0]+b[14]*a[2]+b[4]*a[3]-b[3]*a[4]+b[9]*a[8]-b[8]*a[9]+b[0]*a[10]+b[2]*a[14];
-		res[11]=b[11]*a[0]-b[8]*a[1]+b[6]*a[2]-b[5]*a[3]+b[15]*a[4]-b[3]*a[5]+b[2]*a[6]-b[14]*a[7]-b[1]*a[8]+b[13]*a[9]-b[12]*a[10]+b[0]*a[11]+b[10]*a[12]-b[9]*a[13]+b[7]*a[14]-b[4]*a[15];
-		res[12]=b[12]*a[0]-b[9]*a[1]-b[7]*a[2]+b[15]*a[3]+b[5]*a[4]+b[4]*a[5]-b[14]*a[6]-b[2]*a[7]-b[13]*a[8]-b[1]*a[9]+b[11]*a[10]-b[10]*a[11]+b[0]*a[12]+b[8]*a[13]+b[6]*a[14]-b[3]*a[15];
-		res[13]=b[13]*a[0]-b[10]*a[1]+b[15]*a[2]+b[7]*a[3]-b[6]*a[4]-b[14]*a[5]-b[4]*a[6]+b[3]*a[7]+b[12]*a[8]-b[11]*a[9]-b[1]*a[10]+b[9]*a[11]-b[8]*a[12]+b[0]*a[13]+b[5]*a[14]-b[2]*a[15];
-		res[14]=b[14]*a[0]+b[10]*a[2]+b[9]*a[3]+b[8]*a[4]+b[4]*a[8]+b[3]*a[9]+b[2]*a[10]+b[0]*a[14];
-		res[15]=b[15]*a[0]+b[14]*a[1]+b[13]*a[2]+b[12]*a[3]+b[11]*a[4]+b[10]*a[5]+b[9]*a[6]+b[8]*a[7]+b[7]*a[8]+b[6]*a[9]+b[5]*a[10]-b[4]*a[11]-b[3]*a[12]-b[2]*a[13]-b[1]*a[14]+b[0]*a[15];
-        res
+    fn mul(self: Self, b: Self) -> Self {
+        // f32 and f64 take the vectorised kernel when the `simd` feature is on
+        #[cfg(feature = "simd")]
+        if let Some(res) = super::pga3d_simd::geometric_product(&self, &b) { return res; }
+
+        self.geometric(b)
     }
 }
 
 // Wedge
 // The outer product. (MEET)
-impl BitXor for PGA3D {
-    type Output = PGA3D;
+impl<S: Real> BitXor for PGA3D<S> {
+    type Output = Self;
 
-    fn bitxor(self: PGA3D, b: PGA3D) -> PGA3D {
-        let mut res = PGA3D::zero();
-        let a = self;
-        res[0]=b[0]*a[0];
-		res[1]=b[1]*a[0]+b[0]*a[1];
-		res[2]=b[2]*a[0]+b[0]*a[2];
-		res[3]=b[3]*a[0]+b[0]*a[3];
-		res[4]=b[4]*a[0]+b[0]*a[4];
-		res[5]=b[5]*a[0]+b[2]*a[1]-b[1]*a[2]+b[0]*a[5];
-		res[6]=b[6]*a[0]+b[3]*a[1]-b[1]*a[3]+b[0]*a[6];
-		res[7]=b[7]*a[0]+b[4]*a[1]-b[1]*a[4]+b[0]*a[7];
-		res[8]=b[8]*a[0]+b[3]*a[2]-b[2]*a[3]+b[0]*a[8];
-		res[9]=b[9]*a[0]-b[4]*a[2]+b[2]*a[4]+b[0]*a[9];
-		res[10]=b[10]*a[0]+b[4]*a[3]-b[3]*a[4]+b[0]*a[10];
-		res[11]=b[11]*a[0]-b[8]*a[1]+b[6]*a[2]-b[5]*a[3]-b[3]*a[5]+b[2]*a[6]-b[1]*a[8]+b[0]*a[11];
-		res[12]=b[12]*a[0]-b[9]*a[1]-b[7]*a[2]+b[5]*a[4]+b[4]*a[5]-b[2]*a[7]-b[1]*a[9]+b[0]*a[12];
-		res[13]=b[13]*a[0]-b[10]*a[1]+b[7]*a[3]-b[6]*a[4]-b[4]*a[6]+b[3]*a[7]-b[1]*a[10]+b[0]*a[13];
-		res[14]=b[14]*a[0]+b[10]*a[2]+b[9]*a[3]+b[8]*a[4]+b[4]*a[8]+b[3]*a[9]+b[2]*a[10]+b[0]*a[14];
-		res[15]=b[15]*a[0]+b[14]*a[1]+b[13]*a[2]+b[12]*a[3]+b[11]*a[4]+b[10]*a[5]+b[9]*a[6]+b[8]*a[7]+b[7]*a[8]+b[6]*a[9]+b[5]*a[10]-b[4]*a[11]-b[3]*a[12]-b[2]*a[13]-b[1]*a[14]+b[0]*a[15];
-        res
+    fn bitxor(self: Self, b: Self) -> Self {
+        #[cfg(feature = "simd")]
+        if let Some(res) = super::pga3d_simd::wedge_product(&self, &b) { return res; }
+
+        self.wedge(b)
     }
 }
 
 // Vee
 // The regressive product. (JOIN)
-impl BitAnd for PGA3D {
-    type Output = PGA3D;
+impl<S: Real> BitAnd for PGA3D<S> {
+    type Output = Self;
 
-    fn bitand(self: PGA3D, b: PGA3D) -> PGA3D {
-        let mut res = PGA3D::zero();
-        let a = self;
-        res[15]=1*(a[15]*b[15]);
-		res[14]=-1*(a[14]*-1*b[15]+a[15]*b[14]*-1);
-		res[13]=-1*(a[13]*-1*b[15]+a[15]*b[13]*-1);
-		res[12]=-1*(a[12]*-1*b[15]+a[15]*b[12]*-1);
-		res[11]=-1*(a[11]*-1*b[15]+a[15]*b[11]*-1);
-		res[10]=1*(a[10]*b[15]+a[13]*-1*b[14]*-1-a[14]*-1*b[13]*-1+a[15]*b[10]);
-		res[9]=1*(a[9]*b[15]+a[12]*-1*b[14]*-1-a[14]*-1*b[12]*-1+a[15]*b[9]);
-		res[8]=1*(a[8]*b[15]+a[11]*-1*b[14]*-1-a[14]*-1*b[11]*-1+a[15]*b[8]);
-		res[7]=1*(a[7]*b[15]+a[12]*-1*b[13]*-1-a[13]*-1*b[12]*-1+a[15]*b[7]);
-		res[6]=1*(a[6]*b[15]-a[11]*-1*b[13]*-1+a[13]*-1*b[11]*-1+a[15]*b[6]);
-		res[5]=1*(a[5]*b[15]+a[11]*-1*b[12]*-1-a[12]*-1*b[11]*-1+a[15]*b[5]);
-		res[4]=1*(a[4]*b[15]-a[7]*b[14]*-1+a[9]*b[13]*-1-a[10]*b[12]*-1-a[12]*-1*b[10]+a[13]*-1*b[9]-a[14]*-1*b[7]+a[15]*b[4]);
-		res[3]=1*(a[3]*b[15]-a[6]*b[14]*-1-a[8]*b[13]*-1+a[10]*b[11]*-1+a[11]*-1*b[10]-a[13]*-1*b[8]-a[14]*-1*b[6]+a[15]*b[3]);
-		res[2]=1*(a[2]*b[15]-a[5]*b[14]*-1+a[8]*b[12]*-1-a[9]*b[11]*-1-a[11]*-1*b[9]+a[12]*-1*b[8]-a[14]*-1*b[5]+a[15]*b[2]);
-		res[1]=1*(a[1]*b[15]+a[5]*b[13]*-1+a[6]*b[12]*-1+a[7]*b[11]*-1+a[11]*-1*b[7]+a[12]*-1*b[6]+a[13]*-1*b[5]+a[15]*b[1]);
-		res[0]=1*(a[0]*b[15]+a[1]*b[14]*-1+a[2]*b[13]*-1+a[3]*b[12]*-1+a[4]*b[11]*-1+a[5]*b[10]+a[6]*b[9]+a[7]*b[8]+a[8]*b[7]+a[9]*b[6]+a[10]*b[5]-a[11]*-1*b[4]-a[12]*-1*b[3]-a[13]*-1*b[2]-a[14]*-1*b[1]+a[15]*b[0]);
-        res
+    fn bitand(self: Self, b: Self) -> Self {
+        #[cfg(feature = "simd")]
+        if let Some(res) = super::pga3d_simd::vee_product(&self, &b) { return res; }
+
+        self.vee(b)
     }
 }
 
 // Dot
 // The inner product.
-impl BitOr for PGA3D {
-    type Output = PGA3D;
+impl<S: Real> BitOr for PGA3D<S> {
+    type Output = Self;
 
-    fn bitor(self: PGA3D, b: PGA3D) -> PGA3D {
-        let mut res = PGA3D::zero();
-        let a = self;
-        res[0]=b[0]*a[0]+b[2]*a[2]+b[3]*a[3]+b[4]*a[4]-b[8]*a[8]-b[9]*a[9]-b[10]*a[10]-b[14]*a[14];
-		res[1]=b[1]*a[0]+b[0]*a[1]-b[5]*a[2]-b[6]*a[3]-b[7]*a[4]+b[2]*a[5]+b[3]*a[6]+b[4]*a[7]+b[11]*a[8]+b[12]*a[9]+b[13]*a[10]+b[8]*a[11]+b[9]*a[12]+b[10]*a[13]+b[15]*a[14]-b[14]*a[15];
-		res[2]=b[2]*a[0]+b[0]*a[2]-b[8]*a[3]+b[9]*a[4]+b[3]*a[8]-b[4]*a[9]-b[14]*a[10]-b[10]*a[14];
-		res[3]=b[3]*a[0]+b[8]*a[2]+b[0]*a[3]-b[10]*a[4]-b[2]*a[8]-b[14]*a[9]+b[4]*a[10]-b[9]*a[14];
-		res[4]=b[4]*a[0]-b[9]*a[2]+b[10]*a[3]+b[0]*a[4]-b[14]*a[8]+b[2]*a[9]-b[3]*a[10]-b[8]*a[14];
-		res[5]=b[5]*a[0]-b[11]*a[3]+b[12]*a[4]+b[0]*a[5]-b[15]*a[10]-b[3]*a[11]+b[4]*a[12]-b[10]*a[15];
-		res[6]=b[6]*a[0]+b[11]*a[2]-b[13]*a[4]+b[0]*a[6]-b[15]*a[9]+b[2]*a[11]-b[4]*a[13]-b[9]*a[15];
-		res[7]=b[7]*a[0]-b[12]*a[2]+b[13]*a[3]+b[0]*a[7]-b[15]*a[8]-b[2]*a[12]+b[3]*a[13]-b[8]*a[15];
-		res[8]=b[8]*a[0]+b[14]*a[4]+b[0]*a[8]+b[4]*a[14];
-		res[9]=b[9]*a[0]+b[14]*a[3]+b[0]*a[9]+b[3]*a[14];
-		res[10]=b[10]*a[0]+b[14]*a[2]+b[0]*a[10]+b[2]*a[14];
-		res[11]=b[11]*a[0]+b[15]*a[4]+b[0]*a[11]-b[4]*a[15];
-		res[12]=b[12]*a[0]+b[15]*a[3]+b[0]*a[12]-b[3]*a[15];
-		res[13]=b[13]*a[0]+b[15]*a[2]+b[0]*a[13]-b[2]*a[15];
-		res[14]=b[14]*a[0]+b[0]*a[14];
-		res[15]=b[15]*a[0]+b[0]*a[15];
-        res
+    fn bitor(self: Self, b: Self) -> Self {
+        #[cfg(feature = "simd")]
+        if let Some(res) = super::pga3d_simd::dot_product(&self, &b) { return res; }
+
+        self.dot(b)
     }
 }
 
 // Add
 // Multivector addition
-impl Add for PGA3D {
-    type Output = PGA3D;
+impl<S: Real> Add for PGA3D<S> {
+    type Output = Self;
 
-    fn add(self: PGA3D, b: PGA3D) -> PGA3D {
-        let mut res = PGA3D::zero();
+    fn add(self: Self, b: Self) -> Self {
+        let mut res = Self::zero();
         let a = self;
         res[0] = a[0]+b[0];
 		res[1] = a[1]+b[1];
@@ -365,11 +214,11 @@ impl Add for PGA3D {
 
 // Sub
 // Multivector subtraction
-impl Sub for PGA3D {
-    type Output = PGA3D;
+impl<S: Real> Sub for PGA3D<S> {
+    type Output = Self;
 
-    fn sub(self: PGA3D, b: PGA3D) -> PGA3D {
-        let mut res = PGA3D::zero();
+    fn sub(self: Self, b: Self) -> Self {
+        let mut res = Self::zero();
         let a = self;
         res[0] = a[0]-b[0];
 		res[1] = a[1]-b[1];
@@ -391,41 +240,13 @@ impl Sub for PGA3D {
     }
 }
 
-// smul
-// scalar/multivector multiplication
-impl Mul<PGA3D> for Scalar {
-    type Output = PGA3D;
-
-    fn mul(self: Scalar, b: PGA3D) -> PGA3D {
-        let mut res = PGA3D::zero();
-        let a = self;
-        res[0] = a*b[0];
-        res[1] = a*b[1];
-        res[2] = a*b[2];
-        res[3] = a*b[3];
-        res[4] = a*b[4];
-        res[5] = a*b[5];
-        res[6] = a*b[6];
-        res[7] = a*b[7];
-        res[8] = a*b[8];
-        res[9] = a*b[9];
-        res[10] = a*b[10];
-        res[11] = a*b[11];
-        res[12] = a*b[12];
-        res[13] = a*b[13];
-        res[14] = a*b[14];
-        res[15] = a*b[15];
-        res
-    }
-}
-
 // muls
 // multivector/scalar multiplication
-impl Mul<Scalar> for PGA3D {
-    type Output = PGA3D;
+impl<S: Real> Mul<S> for PGA3D<S> {
+    type Output = Self;
 
-    fn mul(self: PGA3D, b: Scalar) -> PGA3D {
-        let mut res = PGA3D::zero();
+    fn mul(self: Self, b: S) -> Self {
+        let mut res = Self::zero();
         let a = self;
         res[0] = a[0]*b;
         res[1] = a[1]*b;
@@ -445,43 +266,15 @@ impl Mul<Scalar> for PGA3D {
         res[15] = a[15]*b;
         res
     }
-    }
-
-// sadd
-// scalar/multivector addition
-impl Add<PGA3D> for Scalar {
-    type Output = PGA3D;
-
-    fn add(self: Scalar, b: PGA3D) -> PGA3D {
-        let mut res = PGA3D::zero();
-        let a = self;
-        res[0] = a+b[0];
-        res[1] = b[1];
-        res[2] = b[2];
-        res[3] = b[3];
-        res[4] = b[4];
-        res[5] = b[5];
-        res[6] = b[6];
-        res[7] = b[7];
-        res[8] = b[8];
-        res[9] = b[9];
-        res[10] = b[10];
-        res[11] = b[11];
-        res[12] = b[12];
-        res[13] = b[13];
-        res[14] = b[14];
-        res[15] = b[15];
-        res
-    }
 }
 
 // adds
 // multivector/scalar addition
-impl Add<Scalar> for PGA3D {
-    type Output = PGA3D;
+impl<S: Real> Add<S> for PGA3D<S> {
+    type Output = Self;
 
-    fn add(self: PGA3D, b: Scalar) -> PGA3D {
-        let mut res = PGA3D::zero();
+    fn add(self: Self, b: S) -> Self {
+        let mut res = Self::zero();
         let a = self;
         res[0] = a[0]+b;
         res[1] = a[1];
@@ -501,43 +294,15 @@ impl Add<Scalar> for PGA3D {
         res[15] = a[15];
         res
     }
-    }
-
-// ssub
-// scalar/multivector subtraction
-impl ssub<PGA3D> for Scalar {
-    type Output = PGA3D;
-
-    fn ssub(self: Scalar, b: PGA3D) -> PGA3D {
-        let mut res = PGA3D::zero();
-        let a = self;
-        res[0] = a-b[0];
-        res[1] = -b[1];
-        res[2] = -b[2];
-        res[3] = -b[3];
-        res[4] = -b[4];
-        res[5] = -b[5];
-        res[6] = -b[6];
-        res[7] = -b[7];
-        res[8] = -b[8];
-        res[9] = -b[9];
-        res[10] = -b[10];
-        res[11] = -b[11];
-        res[12] = -b[12];
-        res[13] = -b[13];
-        res[14] = -b[14];
-        res[15] = -b[15];
-        res
-    }
 }
 
 // subs
 // multivector/scalar subtraction
-impl subs<PGA3D> for Scalar {
-    type Output = PGA3D;
+impl<S: Real> Sub<S> for PGA3D<S> {
+    type Output = Self;
 
-    fn subs(self: Scalar, b: PGA3D) -> PGA3D {
-        let mut res = PGA3D::zero();
+    fn sub(self: Self, b: S) -> Self {
+        let mut res = Self::zero();
         let a = self;
         res[0] = a[0]-b;
         res[1] = a[1];
@@ -559,114 +324,458 @@ impl subs<PGA3D> for Scalar {
     }
 }
 
-impl PGA3D {
-    pub fn norm(self: Self) -> Scalar {
+// Compound assignment
+// Every binary operator above, in place.
+macro_rules! impl_assign_ops {
+    ($(($Trait:ident, $method:ident, $op:tt, $Rhs:ty)),*) => { $(
+        impl<S: Real> $Trait<$Rhs> for PGA3D<S> {
+            #[inline] fn $method(&mut self, b: $Rhs) { *self = *self $op b; }
+        }
+    )* };
+}
+
+impl_assign_ops!(
+    (AddAssign, add_assign, +, Self), (SubAssign, sub_assign, -, Self), (MulAssign, mul_assign, *, Self),
+    (BitXorAssign, bitxor_assign, ^, Self), (BitAndAssign, bitand_assign, &, Self), (BitOrAssign, bitor_assign, |, Self),
+    (AddAssign, add_assign, +, S), (SubAssign, sub_assign, -, S), (MulAssign, mul_assign, *, S)
+);
+
+// By-reference operators
+// Forward `&a op &b`, `a op &b` and `&a op b` to the by-value products.
+macro_rules! impl_ref_ops {
+    ($(($Trait:ident, $method:ident)),*) => { $(
+        impl<'a, 'b, S: Real> $Trait<&'b PGA3D<S>> for &'a PGA3D<S> {
+            type Output = PGA3D<S>;
+            #[inline] fn $method(self, b: &'b PGA3D<S>) -> PGA3D<S> { (*self).$method(*b) }
+        }
+
+        impl<'b, S: Real> $Trait<&'b PGA3D<S>> for PGA3D<S> {
+            type Output = PGA3D<S>;
+            #[inline] fn $method(self, b: &'b PGA3D<S>) -> PGA3D<S> { self.$method(*b) }
+        }
+
+        impl<'a, S: Real> $Trait<PGA3D<S>> for &'a PGA3D<S> {
+            type Output = PGA3D<S>;
+            #[inline] fn $method(self, b: PGA3D<S>) -> PGA3D<S> { (*self).$method(b) }
+        }
+    )* };
+}
+
+impl_ref_ops!((Add, add), (Sub, sub), (Mul, mul), (BitXor, bitxor), (BitAnd, bitand), (BitOr, bitor));
+
+impl<'a, S: Real> Neg for &'a PGA3D<S> {
+    type Output = PGA3D<S>;
+    #[inline] fn neg(self) -> PGA3D<S> { -*self }
+}
+
+impl<'a, S: Real> Mul<S> for &'a PGA3D<S> {
+    type Output = PGA3D<S>;
+    #[inline] fn mul(self, b: S) -> PGA3D<S> { *self * b }
+}
+
+// Sum
+// Multivector accumulation
+impl<S: Real> Sum for PGA3D<S> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + x)
+    }
+}
+
+impl<'a, S: Real> Sum<&'a PGA3D<S>> for PGA3D<S> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::zero(), |acc, x| acc + *x)
+    }
+}
+
+// Scalar-on-the-left operators can't be written generically over `S` (orphan
+// rules), so they are stamped out for every concrete scalar type instead.
+macro_rules! impl_scalar_lhs_ops {
+    ($($T:ty),*) => { $(
+        // smul
+        // scalar/multivector multiplication
+        impl Mul<PGA3D<$T>> for $T {
+            type Output = PGA3D<$T>;
+
+            fn mul(self: $T, b: PGA3D<$T>) -> PGA3D<$T> {
+                let mut res = PGA3D::zero();
+                let a = self;
+                res[0] = a*b[0];
+                res[1] = a*b[1];
+                res[2] = a*b[2];
+                res[3] = a*b[3];
+                res[4] = a*b[4];
+                res[5] = a*b[5];
+                res[6] = a*b[6];
+                res[7] = a*b[7];
+                res[8] = a*b[8];
+                res[9] = a*b[9];
+                res[10] = a*b[10];
+                res[11] = a*b[11];
+                res[12] = a*b[12];
+                res[13] = a*b[13];
+                res[14] = a*b[14];
+                res[15] = a*b[15];
+                res
+            }
+        }
+
+        // sadd
+        // scalar/multivector addition
+        impl Add<PGA3D<$T>> for $T {
+            type Output = PGA3D<$T>;
+
+            fn add(self: $T, b: PGA3D<$T>) -> PGA3D<$T> {
+                let mut res = PGA3D::zero();
+                let a = self;
+                res[0] = a+b[0];
+                res[1] = b[1];
+                res[2] = b[2];
+                res[3] = b[3];
+                res[4] = b[4];
+                res[5] = b[5];
+                res[6] = b[6];
+                res[7] = b[7];
+                res[8] = b[8];
+                res[9] = b[9];
+                res[10] = b[10];
+                res[11] = b[11];
+                res[12] = b[12];
+                res[13] = b[13];
+                res[14] = b[14];
+                res[15] = b[15];
+                res
+            }
+        }
+
+        // ssub
+        // scalar/multivector subtraction
+        impl Sub<PGA3D<$T>> for $T {
+            type Output = PGA3D<$T>;
+
+            fn sub(self: $T, b: PGA3D<$T>) -> PGA3D<$T> {
+                let mut res = PGA3D::zero();
+                let a = self;
+                res[0] = a-b[0];
+                res[1] = -b[1];
+                res[2] = -b[2];
+                res[3] = -b[3];
+                res[4] = -b[4];
+                res[5] = -b[5];
+                res[6] = -b[6];
+                res[7] = -b[7];
+                res[8] = -b[8];
+                res[9] = -b[9];
+                res[10] = -b[10];
+                res[11] = -b[11];
+                res[12] = -b[12];
+                res[13] = -b[13];
+                res[14] = -b[14];
+                res[15] = -b[15];
+                res
+            }
+        }
+    )* };
+}
+
+impl_scalar_lhs_ops!(f32, f64, I48F16, FixedI128<U62>);
+
+// Grades
+// Projection onto grades and queries about which grades are present.
+impl<S: Real> PGA3D<S> {
+    // The grade-k part (k = 0 .. 4)
+    pub fn grade(self: Self, k: usize) -> Self {
+        let mut res = Self::zero();
+        for i in 0..basis_count {
+            if grades[i] == k { res[i] = self[i]; }
+        }
+        res
+    }
+
+    // The even (scalar + bivector + pseudoscalar) and odd (vector + trivector) parts
+    pub fn even(self: Self) -> Self {
+        (self + self.Involute()) * (S::ONE / S::TWO)
+    }
+
+    pub fn odd(self: Self) -> Self {
+        (self - self.Involute()) * (S::ONE / S::TWO)
+    }
+
+    // Bit k is set when the grade-k part has a coefficient above S::EPS
+    pub fn grade_mask(self: Self) -> u8 {
+        (0..basis_count).filter(|&i| self[i].abs() > S::EPS).fold(0, |m, i| m | (1 << grades[i]))
+    }
+
+    pub fn has_grade(self: Self, k: usize) -> bool {
+        self.grade_mask() & (1 << k) != 0
+    }
+
+    // A single grade that factors into a wedge of vectors. Every homogeneous
+    // element is a blade except bivectors, which must also satisfy B ^ B = 0
+    // (half its e0123 coefficient is e01 e23 + e02 e31 + e03 e12).
+    // Zero counts as a blade.
+    pub fn is_blade(self: Self) -> bool {
+        match self.grade_mask() {
+            0 | 0b00001 | 0b00010 | 0b01000 | 0b10000 => true,
+            0b00100 => (self[5]*self[10] + self[6]*self[9] + self[7]*self[8]).abs() <= S::EPS,
+            _ => false,
+        }
+    }
+}
+
+// Bulk and weight norms
+// The bulk is everything without e0 (the Euclidean part), the weight everything
+// with it (the ideal part). For blades these match `norm` and `inorm`.
+impl<S: Real> PGA3D<S> {
+    pub fn bulk_norm(self: Self) -> S {
+        (0..basis_count).filter(|&i| !basis[i].contains('0')).fold(S::ZERO, |acc, i| acc + self[i] * self[i]).sqrt()
+    }
+
+    pub fn weight_norm(self: Self) -> S {
+        (0..basis_count).filter(|&i| basis[i].contains('0')).fold(S::ZERO, |acc, i| acc + self[i] * self[i]).sqrt()
+    }
+}
+
+impl<S: Real> PGA3D<S> {
+    pub fn norm(self: Self) -> S {
         let scalar_part = (self * self.Conjugate())[0];
 
         scalar_part.abs().sqrt()
     }
 
-    pub fn inorm(self: Self) -> Scalar {
+    pub fn inorm(self: Self) -> S {
         self.Dual().norm()
     }
 
     pub fn normalized(self: Self) -> Self {
-        self * (ONE / self.norm())
+        self * (S::ONE / self.norm())
+    }
+
+    // `normalized` without the division by zero (which panics for fixed-point)
+    pub fn try_normalized(self: Self) -> Option<Self> {
+        let n = self.norm();
+        if n > S::EPS { Some(self * (S::ONE / n)) } else { None }
+    }
+
+    pub fn normalized_or_zero(self: Self) -> Self {
+        self.try_normalized().unwrap_or(Self::zero())
     }
     
     
     // A rotor (Euclidean line) and translator (Ideal line)
-    pub fn rotor(angle: Scalar, line: Self) -> Self {
-        (angle / TWO).cos() + (angle / TWO).sin() * line.normalized()
+    pub fn rotor(angle: S, line: Self) -> Self {
+        let (sin, cos) = (angle / S::TWO).sin_cos();
+        line.normalized() * sin + cos
     }
 
-    pub fn translator(dist: Scalar, line: Self) -> Self {
-        ONE + dist / TWO * line
+    pub fn translator(dist: S, line: Self) -> Self {
+        line * (dist / S::TWO) + S::ONE
     }
 
     // A plane is defined using its homogenous equation ax + by + cz + d = 0
-    pub fn plane(a: Scalar, b: Scalar, c:Scalar, d:Scalar) -> Self {
-        a * e1 + b * e2 + c * e3 + d * e0
+    pub fn plane(a: S, b: S, c: S, d: S) -> Self {
+        Self::e1 * a + Self::e2 * b + Self::e3 * c + Self::e0 * d
     }
 
-    // PGA lines are bivectors.
-    pub fn e01() -> Self { e0^e1 } 
-    pub fn e02() -> Self { e0^e2 }
-    pub fn e03() -> Self { e0^e3 }
-    pub fn e12() -> Self { e1^e2 } 
-    pub fn e31() -> Self { e3^e1 }
-    pub fn e23() -> Self { e2^e3 }
-
-    // PGA points are trivectors.
-    pub fn e123() -> Self { e1^e2^e3 }
-    pub fn e032() -> Self { e0^e3^e2 }
-    pub fn e013() -> Self { e0^e1^e3 }
-    pub fn e021() -> Self { e0^e2^e1 }
+    // PGA lines are bivectors (e01 .. e23), PGA points are trivectors
+    // (e021 .. e123); both are available as the basis constants above.
 
     // A point is just a homogeneous point, euclidean coordinates plus the origin
-    pub fn point(x: Scalar, y: Scalar, z:Scalar) -> Self {
-         Self::e123() + x * Self::e032() + y * Self::e013() + z * Self::e021()
+    pub fn point(x: S, y: S, z: S) -> Self {
+         Self::e123 + Self::e032 * x + Self::e013 * y + Self::e021 * z
     }
 
     // for our toy problem (generate points on the surface of a torus)
     // we start with a function that generates motors.
     // circle(t) with t going from 0 to 1.
-    pub fn circle(t: Scalar, radius: Scalar, line: Self) -> Self {
-        Self::rotor(t * TWO * PI, line) * Self::translator(radius, e1 * e0)
+    pub fn circle(t: S, radius: S, line: Self) -> Self {
+        Self::rotor(t * S::TWO * S::PI, line) * Self::translator(radius, Self::e1 * Self::e0)
     }
 
     // a torus is now the product of two circles.
-    pub fn torus(s: Scalar, t: Scalar, r1: Scalar, l1: Self, r2: Scalar, l2: Self) -> Self {
+    pub fn torus(s: S, t: S, r1: S, l1: Self, r2: S, l2: Self) -> Self {
         Self::circle(s, r2, l2) * Self::circle(t, r1, l1)
     }
 
     // and to sample its points we simply sandwich the origin ..
-    pub fn point_on_torus(s: Scalar, t: Scalar) -> Self {
-        let to: Self = Self::torus(s, t, 0.25, Self::e12(), 0.6, Self::e31());
+    pub fn point_on_torus(s: S, t: S) -> Self {
+        let to: Self = Self::torus(s, t, S::from_f64(0.25), Self::e12, S::from_f64(0.6), Self::e31);
 
-        to * Self::e123() * to.Reverse()
+        to * Self::e123 * to.Reverse()
     }
+}
 
+#[cfg(test)]
+mod tests {
+    use super::*;
 
+    const EPS_FIXED: f64 = 0.001;
 
-}
+    //  Deterministic, non-trivial multivectors with every blade populated
+    fn sample<S: Real>(seed: f64) -> PGA3D<S> {
+        let mut res = PGA3D::zero();
+        for i in 0..basis_count {
+            res[i] = S::from_f64(((i as f64 + 1.0) * seed).sin());
+        }
+        res
+    }
 
+    fn assert_close<S: Real>(got: PGA3D<S>, expect: PGA3D<f64>, eps: f64) {
+        for i in 0..basis_count {
+            let diff = (got[i].to_f64() - expect[i]).abs();
+            assert!(diff < eps, "blade {} differs: {} vs {}", basis[i], got[i], expect[i]);
+        }
+    }
 
-fn main() {
+    fn check_products<S: Real>(eps: f64) {
+        let (a64, b64) = (sample::<f64>(0.7), sample::<f64>(1.3));
+        let (a, b) = (sample::<S>(0.7), sample::<S>(1.3));
 
-    // Elements of the even subalgebra (scalar + bivector + pss) of unit length are motors
-    let rot = PGA3D::rotor(PI / 2.0, e1 * e2);
+        assert_close(a * b, a64 * b64, eps);
+        assert_close(a ^ b, a64 ^ b64, eps);
+        assert_close(a & b, a64 & b64, eps);
+        assert_close(a | b, a64 | b64, eps);
+        assert_close(a + b - a, b64, eps);
+    }
 
-    // The outer product ^ is the MEET. Here we intersect the yz (x=0) and xz (y=0) planes.
-    let ax_z = e1 ^ e2;
-    
-    // line and plane meet in point. We intersect the line along the z-axis (x=0,y=0) with the xy (z=0) plane.
-    let orig = ax_z ^ e3;
-    
-    // We can also easily create points and join them into a line using the regressive (vee, &) product.
-    let px = PGA3D::point(1.0, 0.0, 0.0);
-    let line = orig & px;
-    
-    // Lets also create the plane with equation 2x + z - 3 = 0
-    let p = PGA3D::plane(2.0, 0.0, 1.0, -3.0);
-    
-    // rotations work on all elements
-    let rotated_plane = rot * p * rot.Reverse();
-    let rotated_line  = rot * line * rot.Reverse();
-    let rotated_point = rot * px * rot.Reverse();
-    
-    // See the 3D PGA Cheat sheet for a huge collection of useful formulas
-    let point_on_plane = (p | px) * p;
-
-    // Some output
-    println!("a point       : {}", px);
-    println!("a line        : {}", line);
-    println!("a plane       : {}", p);
-    println!("a rotor       : {}", rot);
-    println!("rotated line  : {}", rotated_line);
-    println!("rotated point : {}", rotated_point);
-    println!("rotated plane : {}", rotated_plane);
-    println!("point on plane: {}", point_on_plane.normalized());
-    println!("point on torus: {}", PGA3D::point_on_torus(0.0, 0.0));
-
-}
\ No newline at end of file
+    //  Products
+    #[test] fn test_products_f32() { check_products::<f32>(1e-5); }
+    #[test] fn test_products_fixed() { check_products::<I48F16>(EPS_FIXED); }
+    #[test] fn test_products_wide() { check_products::<FixedI128<U62>>(1e-12); }
+
+    //  The build-time tables against the algebra's defining identities
+    #[test] fn test_generated_tables() {
+        let vectors = [PGA3D::<f64>::e0, PGA3D::e1, PGA3D::e2, PGA3D::e3];
+        for (i, &u) in vectors.iter().enumerate() {
+            assert_eq!(u * u, PGA3D::zero() + if i == 0 { 0.0 } else { 1.0 });
+            for &v in &vectors {
+                assert_eq!(u ^ v, (u * v - v * u) * 0.5);
+                assert_eq!(u | v, (u * v + v * u) * 0.5);
+            }
+        }
+        assert_eq!(PGA3D::<f64>::e0123, PGA3D::e0 * PGA3D::e1 * PGA3D::e2 * PGA3D::e3);
+        assert_eq!(PGA3D::<f64>::e021, PGA3D::e0 * PGA3D::e2 * PGA3D::e1);
+
+        let (a, b, c) = (sample::<f64>(0.7), sample::<f64>(1.3), sample::<f64>(2.1));
+        assert_close((a * b) * c, a * (b * c), 1e-12);
+        assert_close((a ^ b) ^ c, a ^ (b ^ c), 1e-12);
+        assert_close((a & b) & c, a & (b & c), 1e-12);
+        assert_close((a * b).Reverse(), b.Reverse() * a.Reverse(), 1e-12);
+        assert_eq!(!!a, a);
+    }
+
+    #[test] fn test_vee_joins_points() {
+        //  The join of the origin and (1, 0, 0) is the x axis.
+        let p = PGA3D::<I48F16>::point(I48F16::ZERO, I48F16::ZERO, I48F16::ZERO);
+        let q = PGA3D::<I48F16>::point(I48F16::ONE, I48F16::ZERO, I48F16::ZERO);
+        let line = p & q;
+        assert!(line[8].abs() < I48F16::DELTA && line[9].abs() < I48F16::DELTA);
+        assert_eq!(line[10], -I48F16::ONE);
+    }
+
+    //  Rotations
+    #[test] fn test_rotor_fixed() {
+        type S = I48F16;
+        //  A positive angle about e12 turns x towards -y, so rotate the other way.
+        let rot = PGA3D::<S>::rotor(-S::PI / S::TWO, PGA3D::e12);
+        let res = rot * PGA3D::point(S::ONE, S::ZERO, S::ZERO) * rot.Reverse();
+
+        //  Expected: (0, 1, 0)
+        let w = res[14].to_f64();
+        assert!((res[13].to_f64() / w).abs() < EPS_FIXED, "X should be 0, got {}", res);
+        assert!((res[12].to_f64() / w - 1.0).abs() < EPS_FIXED, "Y should be 1, got {}", res);
+    }
+
+    //  Operators
+    #[test] fn test_assign_and_ref_ops() {
+        let (a, b) = (sample::<f64>(0.7), sample::<f64>(1.3));
+
+        let mut c = a;
+        c *= b;
+        assert_eq!(c, a * b);
+        c -= a * b;
+        assert_close(c, PGA3D::zero(), 1e-12);
+        c += 2.0;
+        c *= 3.0;
+        assert_eq!(c, PGA3D::zero() + 6.0);
+
+        let (ra, rb) = (&a, &b);
+        assert_eq!(ra * rb, a * b);
+        assert_eq!(a ^ rb, a ^ b);
+        assert_eq!(ra & b, a & b);
+        assert_eq!(-ra, 0.0 - a);
+        assert_eq!(-a + a, PGA3D::zero());
+        assert_eq!([a, b, a].iter().sum::<PGA3D<f64>>(), a + b + a);
+        assert_eq!(vec![a, b].into_iter().sum::<PGA3D<f64>>(), a + b);
+    }
+
+    //  Grades and norms
+    #[test] fn test_grades() {
+        let a = sample::<f64>(0.7);
+        let sum = (0..5).map(|k| a.grade(k)).sum::<PGA3D<f64>>();
+        assert_close(sum, a, 1e-12);
+        assert_close(a.even() + a.odd(), a, 1e-12);
+        assert_eq!(a.even().grade_mask(), 0b10101);
+        assert_eq!(a.odd().grade_mask(), 0b01010);
+        assert!(a.grade(2).has_grade(2) && !a.grade(2).has_grade(1));
+
+        let e = PGA3D::<f64>::e1 * 2.0 + PGA3D::e0;
+        assert!(e.is_blade());
+        assert!((PGA3D::<f64>::e12 + PGA3D::e31).is_blade());
+        assert!(!(PGA3D::<f64>::e12 + PGA3D::e03).is_blade());
+        assert!(!(PGA3D::<f64>::e1 + PGA3D::e12).is_blade());
+    }
+
+    #[test] fn test_bulk_weight_norms() {
+        let line = PGA3D::<f64>::point(1.0, 2.0, 3.0) & PGA3D::point(-1.0, 0.5, 2.0);
+        assert!((line.bulk_norm() - line.norm()).abs() < 1e-12);
+        assert!((line.weight_norm() - line.inorm()).abs() < 1e-12);
+
+        let p = PGA3D::<f64>::plane(3.0, 0.0, 4.0, -2.0);
+        assert_eq!(p.bulk_norm(), 5.0);
+        assert_eq!(p.weight_norm(), 2.0);
+    }
+
+    #[test] fn test_try_normalized() {
+        assert_eq!(PGA3D::<I48F16>::zero().try_normalized(), None);
+        assert_eq!(PGA3D::<I48F16>::zero().normalized_or_zero(), PGA3D::zero());
+
+        let p = PGA3D::<I48F16>::plane(I48F16::lit("3"), I48F16::ZERO, I48F16::lit("4"), I48F16::ONE);
+        assert!((p.try_normalized().unwrap()[2].to_f64() - 0.6).abs() < EPS_FIXED);
+    }
+
+    //  The original bivector.net sample, checked against its printed output
+    #[test] fn test_demo() {
+        let (e1, e2, e3) = (PGA3D::<f64>::e1, PGA3D::<f64>::e2, PGA3D::<f64>::e3);
+
+        //  Elements of the even subalgebra (scalar + bivector + pss) of unit length are motors
+        let rot = PGA3D::rotor(f64::PI / 2.0, e1 * e2);
+
+        //  The outer product ^ is the MEET. Intersect the yz (x=0) and xz (y=0) planes, then the xy plane.
+        let ax_z = e1 ^ e2;
+        let orig = ax_z ^ e3;
+
+        //  Points join into a line using the regressive (vee, &) product.
+        let px = PGA3D::point(1.0, 0.0, 0.0);
+        let line = orig & px;
+
+        //  The plane with equation 2x + z - 3 = 0
+        let p = PGA3D::plane(2.0, 0.0, 1.0, -3.0);
+
+        //  Rotations work on all elements
+        let rotated_plane = rot * p * rot.Reverse();
+        let rotated_line  = rot * line * rot.Reverse();
+        let rotated_point = rot * px * rot.Reverse();
+
+        let point_on_plane = (p | px) * p;
+
+        assert_eq!(format!("{}", px), "1e032 + 1e123");
+        assert_eq!(format!("{}", line), "-1e23");
+        assert_eq!(format!("{}", p), "-3e0 + 2e1 + 1e3");
+        assert_eq!(format!("{}", rot), "0.7071068 + 0.7071068e12");
+        assert_eq!(format!("{}", rotated_line), "1e31");
+        assert_eq!(format!("{}", rotated_point), "-1e013 + 1e123");
+        assert_eq!(format!("{}", rotated_plane), "-3e0 + -2e2 + 1e3");
+        assert_eq!(format!("{}", point_on_plane.normalized()), "0.2e021 + 1.4e032 + 1e123");
+        assert_eq!(format!("{}", PGA3D::<f64>::point_on_torus(0.0, 0.0)), "0.85e032 + 1e123");
+    }
+}
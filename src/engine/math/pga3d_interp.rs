@@ -0,0 +1,169 @@
+//  	Imports
+use super::scalar::Real;
+use super::pga3d_types::{Line, Motor, Rotor};
+
+//		Interpolation
+//	Everything here works in the motor's tangent space: the relative motion
+//	between two keys is taken to its bivector with `log`, scaled, and brought
+//	back with `exp`. This moves position and orientation together as one
+//	rigid screw motion instead of lerping them separately.
+
+//	Relative motion `from` -> `to` in the world frame, on the short side of the double cover
+#[inline] fn relative<S: Real>(from: Motor<S>, to: Motor<S>) -> Line<S> {
+	let d = to * from.reverse();
+	if d.s < S::ZERO { (-d).log() } else { d.log() }
+}
+
+impl<S: Real> Motor<S> {
+	//	Screw-linear interpolation: constant-twist path from `a` (t = 0) to `b` (t = 1)
+	pub fn sclerp(a: Self, b: Self, t: S) -> Self {
+		Self::exp(relative(a, b) * t) * a
+	}
+
+	//	Alias for `sclerp`, matching the rotor/quaternion name
+	#[inline] pub fn slerp(a: Self, b: Self, t: S) -> Self { Self::sclerp(a, b, t) }
+
+	//	Weighted blend of motors; weights are expected to sum to one.
+	//	The average is taken in the tangent space of the first motor, which is
+	//	exact for two keys and a good approximation while the keys stay close.
+	pub fn blend(motors: &[(Self, S)]) -> Self {
+		let Some(&(base, _)) = motors.first() else { return Self::IDENTITY };
+
+		let mut acc = Line::ZERO;
+		for &(m, w) in &motors[1..] {
+			acc += relative(base, m) * w;
+		}
+		Self::exp(acc) * base
+	}
+}
+
+impl<S: Real> Rotor<S> {
+	//	Spherical-linear interpolation from `a` (t = 0) to `b` (t = 1)
+	pub fn slerp(a: Self, b: Self, t: S) -> Self {
+		let m = Motor::sclerp(a.into(), b.into(), t);
+		Self::new(m.s, m.e12, m.e31, m.e23)
+	}
+}
+
+//		Splines
+//	Cubic motor spline through uniformly spaced keys, e.g. fixed-rate pose snapshots.
+//	Each segment is a Bézier curve evaluated by de Casteljau with `sclerp`; the
+//	inner control points come from Catmull-Rom tangents in the bivector space,
+//	so the curve passes through every key and a constant twist is reproduced exactly.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct MotorSpline<S: Real> {
+	pub keys: Vec<Motor<S>>,
+}
+
+impl<S: Real> MotorSpline<S> {
+	#[inline] pub fn new(keys: Vec<Motor<S>>) -> Self { Self { keys } }
+
+	//	Append the newest snapshot
+	#[inline] pub fn push(&mut self, key: Motor<S>) { self.keys.push(key); }
+
+	//	Tangent (half-twist per key interval) at key `i`
+	fn tangent(&self, i: usize) -> Line<S> {
+		let last = self.keys.len() - 1;
+		match i {
+			0 => relative(self.keys[0], self.keys[1]),
+			_ if i == last => relative(self.keys[last - 1], self.keys[last]),
+			_ => (relative(self.keys[i - 1], self.keys[i]) + relative(self.keys[i], self.keys[i + 1])) / S::TWO,
+		}
+	}
+
+	//	Sample at `t` measured in key intervals, clamped to [0, keys.len() - 1]
+	pub fn sample(&self, t: S) -> Motor<S> {
+		match self.keys.len() {
+			0 => return Motor::IDENTITY,
+			1 => return self.keys[0],
+			_ => {}
+		}
+
+		//	Segment and local parameter
+		let last = self.keys.len() - 1;
+		if t <= S::ZERO { return self.keys[0]; }
+		if t >= S::from_f64(last as f64) { return self.keys[last]; }
+		let i = t.to_f64().floor() as usize;
+		let u = t - S::from_f64(i as f64);
+
+		//	Bézier control points
+		let three = S::ONE + S::TWO;
+		let p0 = self.keys[i];
+		let p3 = self.keys[i + 1];
+		let p1 = Motor::exp(self.tangent(i) / three) * p0;
+		let p2 = Motor::exp(-self.tangent(i + 1) / three) * p3;
+
+		//	de Casteljau
+		let (a, b, c) = (Motor::sclerp(p0, p1, u), Motor::sclerp(p1, p2, u), Motor::sclerp(p2, p3, u));
+		let (d, e) = (Motor::sclerp(a, b, u), Motor::sclerp(b, c, u));
+		Motor::sclerp(d, e, u)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use super::super::pga3d_float::PGA3D;
+
+	const EPS: f64 = 1e-9;
+
+	fn assert_close(a: Motor<f64>, b: Motor<f64>) {
+		let (a, b) = (PGA3D::from(a), PGA3D::from(b));
+		for i in 0..16 {
+			assert!((a[i] - b[i]).abs() < EPS, "{} vs {}", a, b);
+		}
+	}
+
+	fn screw() -> Line<f64> { Line::new(0.3, -1.2, 0.8, 0.4, -0.25, 0.6) }
+
+	//		Tests
+	//	Interpolation
+	#[test] fn test_sclerp_endpoints() {
+		let a = Motor::exp(screw() * 0.2);
+		let b = Motor::exp(screw() * -0.7) * Motor::from(Rotor::from_axis_angle(1.0, 0.0, 0.0, 0.3));
+		assert_close(Motor::sclerp(a, b, 0.0), a);
+		assert_close(Motor::sclerp(a, b, 1.0), b);
+
+		//	The double cover must not matter
+		assert_close(Motor::sclerp(a, -b, 1.0), b);
+	}
+
+	#[test] fn test_sclerp_constant_twist() {
+		let (a, b) = (Motor::exp(screw() * 0.1), Motor::exp(screw() * 0.9));
+		assert_close(Motor::sclerp(a, b, 0.25), Motor::exp(screw() * 0.3));
+	}
+
+	#[test] fn test_rotor_slerp() {
+		let a = Rotor::<f64>::IDENTITY;
+		let b = Rotor::from_axis_angle(0.0, 1.0, 0.0, 1.2);
+		let mid = Rotor::slerp(a, b, 0.5);
+		assert_close(mid.into(), Rotor::from_axis_angle(0.0, 1.0, 0.0, 0.6).into());
+	}
+
+	#[test] fn test_blend() {
+		let (a, b) = (Motor::exp(screw() * 0.1), Motor::exp(screw() * -0.4));
+		assert_close(Motor::blend(&[(a, 0.5), (b, 0.5)]), Motor::sclerp(a, b, 0.5));
+		assert_close(Motor::blend(&[]), Motor::IDENTITY);
+	}
+
+	//	Splines
+	#[test] fn test_spline_hits_keys() {
+		let keys: Vec<_> = (0..5).map(|i| {
+			let t = i as f64;
+			Motor::exp(screw() * (0.1 * t * t)) * Motor::from(Rotor::from_axis_angle(0.0, 0.0, 1.0, t))
+		}).collect();
+		let spline = MotorSpline::new(keys.clone());
+		for (i, &k) in keys.iter().enumerate() {
+			assert_close(spline.sample(i as f64), k);
+		}
+	}
+
+	#[test] fn test_spline_constant_twist() {
+		let mut spline = MotorSpline::default();
+		for i in 0..4 {
+			spline.push(Motor::exp(screw() * (0.2 * i as f64)));
+		}
+		assert_close(spline.sample(1.3), Motor::exp(screw() * 0.26));
+		assert_close(spline.sample(0.5), Motor::exp(screw() * 0.1));
+	}
+}
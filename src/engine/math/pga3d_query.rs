@@ -0,0 +1,447 @@
+//  	Imports
+use super::scalar::Real;
+use super::pga3d_types::{Plane, Line, Point, Motor};
+
+//		Geometric queries
+//	Distances, angles, projections and intersections between typed PGA3D
+//	elements. Inputs need not be normalised; every query normalises what it
+//	needs so results come out in world units and radians. Ideal elements
+//	(zero weight: points at infinity, the plane at infinity, lines in it) have
+//	no Euclidean distance, so distances to them are `None`, as are projections
+//	from or onto them, and intersections with them come out `Ideal`,
+//	`Coincident` or `Disjoint`.
+
+//	Outcome of intersecting two elements
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Intersection<T> {
+	//	Proper (Euclidean) intersection
+	Proper(T),
+	//	Parallel elements meet at infinity in this ideal element
+	Ideal(T),
+	//	One element lies in the other
+	Coincident,
+	//	No common element, not even at infinity (skew lines)
+	Disjoint,
+}
+
+//		Query traits
+pub trait Distance<T> {
+	type Output;
+	fn distance(self, other: T) -> Self::Output;
+}
+
+pub trait Angle<T> {
+	type Output;
+	fn angle(self, other: T) -> Self::Output;
+}
+
+//	Projection of `self` onto `onto`; the rejection is what is left of the
+//	normalised element once its normalised projection is taken away. `None`
+//	for ideal inputs, and where the projection has no weight either (a line
+//	onto a plane it is orthogonal to, say).
+pub trait Project<T>: Sized {
+	fn project(self, onto: T) -> Option<Self>;
+	fn reject(self, from: T) -> Option<Self>;
+}
+
+pub trait Intersect<T> {
+	type Output;
+	fn intersect(self, other: T) -> Intersection<Self::Output>;
+}
+
+//		Distances
+//	Point - Point: Euclidean norm of the joining line
+impl<S: Real> Distance<Point<S>> for Point<S> {
+	type Output = Option<S>;
+	#[inline] fn distance(self, other: Point<S>) -> Option<S> {
+		Some((self.try_normalized()? & other.try_normalized()?).norm())
+	}
+}
+
+//	Point - Plane: signed, positive on the side the plane normal points to
+impl<S: Real> Distance<Plane<S>> for Point<S> {
+	type Output = Option<S>;
+	#[inline] fn distance(self, plane: Plane<S>) -> Option<S> {
+		let (p, n) = (self.try_normalized()?, plane.try_normalized()?);
+		Some(n.e1 * p.e032 + n.e2 * p.e013 + n.e3 * p.e021 + n.e0)
+	}
+}
+
+impl<S: Real> Distance<Point<S>> for Plane<S> {
+	type Output = Option<S>;
+	#[inline] fn distance(self, point: Point<S>) -> Option<S> { point.distance(self) }
+}
+
+//	Point - Line: Euclidean norm of the plane joining them
+impl<S: Real> Distance<Line<S>> for Point<S> {
+	type Output = Option<S>;
+	#[inline] fn distance(self, line: Line<S>) -> Option<S> {
+		Some((line.try_normalized()? & self.try_normalized()?).norm())
+	}
+}
+
+impl<S: Real> Distance<Point<S>> for Line<S> {
+	type Output = Option<S>;
+	#[inline] fn distance(self, point: Point<S>) -> Option<S> { point.distance(self) }
+}
+
+//	Line - Line: from the product of the normalised lines, whose e0123 part is
+//	d·sin θ and whose ideal bivector part carries d once the lines are parallel
+impl<S: Real> Distance<Line<S>> for Line<S> {
+	type Output = Option<S>;
+	fn distance(self, other: Line<S>) -> Option<S> {
+		let m: Motor<S> = self.try_normalized()? * other.try_normalized()?;
+		let sin = (m.e12 * m.e12 + m.e31 * m.e31 + m.e23 * m.e23).sqrt();
+		if sin > S::EPS {
+			Some((m.e0123 / sin).abs())
+		} else {
+			Some((m.e01 * m.e01 + m.e02 * m.e02 + m.e03 * m.e03).sqrt())
+		}
+	}
+}
+
+//		Angles
+//	Both are atan2(|sin|, cos) of unnormalised quantities that share the same
+//	scale, which is well-conditioned near 0 and π and needs no normalisation.
+impl<S: Real> Angle<Plane<S>> for Plane<S> {
+	type Output = S;
+	#[inline] fn angle(self, other: Plane<S>) -> S {
+		(self ^ other).norm().atan2(self | other)
+	}
+}
+
+impl<S: Real> Angle<Line<S>> for Line<S> {
+	type Output = S;
+	#[inline] fn angle(self, other: Line<S>) -> S {
+		let m: Motor<S> = self * other;
+		(m.e12 * m.e12 + m.e31 * m.e31 + m.e23 * m.e23).sqrt().atan2(-m.s)
+	}
+}
+
+//		Projections
+//	All follow (x · y) y⁻¹: the inner product builds the element through `x`
+//	orthogonal to `y`, and the meet (or another inner product) lands it on `y`.
+//	Normalised points and lines square to -1, so their inverse is their negation.
+//	Between elements of the same grade x · y is a scalar, leaving y itself,
+//	turned to face x (a point onto a point is that point).
+macro_rules! impl_project {
+	($X:ident onto $Y:ident, |$x:ident, $y:ident| $proj:expr) => {
+		impl<S: Real> Project<$Y<S>> for $X<S> {
+			#[inline] fn project(self, onto: $Y<S>) -> Option<Self> {
+				let ($x, $y) = (self.try_normalized()?, onto.try_normalized()?);
+				$proj.try_normalized()
+			}
+			#[inline] fn reject(self, from: $Y<S>) -> Option<Self> {
+				Some(self.try_normalized()? - self.project(from)?)
+			}
+		}
+	};
+}
+
+impl_project!(Point onto Point, |x, y| y * -(x | y));
+impl_project!(Line onto Line, |x, y| y * -(x | y));
+impl_project!(Plane onto Plane, |x, y| y * (x | y));
+
+impl_project!(Point onto Plane, |x, y| (x | y) ^ y);
+impl_project!(Point onto Line, |x, y| (x | y) ^ y);
+impl_project!(Line onto Plane, |x, y| (x | y) ^ y);
+impl_project!(Line onto Point, |x, y| -((x | y) | y));
+impl_project!(Plane onto Point, |x, y| -((x | y) | y));
+impl_project!(Plane onto Line, |x, y| -((x | y) | y));
+
+//		Intersections
+//	Ideal inputs are met as they are: their meet has no weight either, which
+//	lands it in the ideal or coincident branch without dividing by it.
+
+//	Plane - Plane: the meet is a line, ideal when the planes are parallel or
+//	one is the plane at infinity
+impl<S: Real> Intersect<Plane<S>> for Plane<S> {
+	type Output = Line<S>;
+	fn intersect(self, other: Plane<S>) -> Intersection<Line<S>> {
+		let l = self.try_normalized().unwrap_or(self) ^ other.try_normalized().unwrap_or(other);
+		let ideal = (l.e01 * l.e01 + l.e02 * l.e02 + l.e03 * l.e03).sqrt();
+		if l.norm() > S::EPS { Intersection::Proper(l) }
+		else if ideal > S::EPS { Intersection::Ideal(l / ideal) }
+		else { Intersection::Coincident }
+	}
+}
+
+//	Line - Plane: the meet is a point, ideal (the line's direction) when parallel
+impl<S: Real> Intersect<Plane<S>> for Line<S> {
+	type Output = Point<S>;
+	fn intersect(self, plane: Plane<S>) -> Intersection<Point<S>> {
+		let p = self.try_normalized().unwrap_or(self) ^ plane.try_normalized().unwrap_or(plane);
+		if p.e123.abs() > S::EPS { Intersection::Proper(p.normalized()) }
+		else { direction_or(p, Intersection::Coincident) }
+	}
+}
+
+impl<S: Real> Intersect<Line<S>> for Plane<S> {
+	type Output = Point<S>;
+	#[inline] fn intersect(self, line: Line<S>) -> Intersection<Point<S>> { line.intersect(self) }
+}
+
+//	Line - Line: coplanar lines meet in a point, parallel ones in their direction
+impl<S: Real> Intersect<Line<S>> for Line<S> {
+	type Output = Point<S>;
+	fn intersect(self, other: Line<S>) -> Intersection<Point<S>> {
+		let (a, b) = match (self.try_normalized(), other.try_normalized()) {
+			(Some(a), Some(b)) => (a, b),
+			(None, _) => return intersect_ideal(self, other),
+			(_, None) => return intersect_ideal(other, self),
+		};
+		let m: Motor<S> = a * b;
+		let sin = (m.e12 * m.e12 + m.e31 * m.e31 + m.e23 * m.e23).sqrt();
+
+		//	Ideal point (direction) of `a`
+		let dir = Plane::new(S::ONE, S::ZERO, S::ZERO, S::ZERO) ^ a;
+		if sin <= S::EPS {
+			return if a.distance(b).is_some_and(|d| d > S::EPS) { direction_or(dir, Intersection::Coincident) }
+				else { Intersection::Coincident };
+		}
+		if (m.e0123 / sin).abs() > S::EPS { return Intersection::Disjoint; }
+
+		//	Meet `b` with the plane through `a` orthogonal to their common plane
+		let p = b ^ (a | (dir & b));
+		Intersection::Proper(p.normalized())
+	}
+}
+
+//	Line - Line with `a` ideal: a line in the plane at infinity, the horizon of
+//	the planes whose normal n is its (e01, e02, e03) part. It meets a Euclidean
+//	line in that line's direction d if n · d = 0, and another ideal line in the
+//	direction both horizons share, n × n'.
+fn intersect_ideal<S: Real>(a: Line<S>, b: Line<S>) -> Intersection<Point<S>> {
+	let na = (a.e01 * a.e01 + a.e02 * a.e02 + a.e03 * a.e03).sqrt();
+	if na <= S::EPS { return Intersection::Disjoint; }
+	let (x, y, z) = (a.e01 / na, a.e02 / na, a.e03 / na);
+
+	if let Some(b) = b.try_normalized() {
+		let d = Plane::new(S::ONE, S::ZERO, S::ZERO, S::ZERO) ^ b;
+		return if (x * d.e032 + y * d.e013 + z * d.e021).abs() <= S::EPS {
+			direction_or(d, Intersection::Disjoint)
+		} else {
+			Intersection::Disjoint
+		};
+	}
+
+	let nb = (b.e01 * b.e01 + b.e02 * b.e02 + b.e03 * b.e03).sqrt();
+	if nb <= S::EPS { return Intersection::Disjoint; }
+	let (u, v, w) = (b.e01 / nb, b.e02 / nb, b.e03 / nb);
+	direction_or(Point::new(x * v - y * u, z * u - x * w, y * w - z * v, S::ZERO), Intersection::Coincident)
+}
+
+//	Classify a point with (near) zero weight as an ideal direction, or fall back
+fn direction_or<S: Real>(p: Point<S>, fallback: Intersection<Point<S>>) -> Intersection<Point<S>> {
+	let n = (p.e021 * p.e021 + p.e013 * p.e013 + p.e032 * p.e032).sqrt();
+	if n > S::EPS {
+		Intersection::Ideal(Point::new(p.e021 / n, p.e013 / n, p.e032 / n, S::ZERO))
+	} else {
+		fallback
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::f64::consts::{FRAC_PI_2, FRAC_PI_4};
+
+	const EPS: f64 = 1e-9;
+
+	fn close(a: f64, b: f64) -> bool { (a - b).abs() < EPS }
+	fn assert_point(p: Point<f64>, x: f64, y: f64, z: f64) {
+		let p = p.normalized();
+		assert!(close(p.e032, x) && close(p.e013, y) && close(p.e021, z), "{} vs ({}, {}, {})", p, x, y, z);
+	}
+
+	//	z = 2, scaled to check normalisation
+	fn plane_z2() -> Plane<f64> { Plane::from_equation(0.0, 0.0, 3.0, -6.0) }
+	fn join(a: [f64; 3], b: [f64; 3]) -> Line<f64> { Point::at(a[0], a[1], a[2]) & Point::at(b[0], b[1], b[2]) }
+
+	//		Tests
+	//	Distances
+	#[test] fn test_distances() {
+		let p = Point::at(1.0, 2.0, 5.0) * 2.0;
+		assert!(close(p.distance(Point::at(1.0, 2.0, 1.0)).unwrap(), 4.0));
+		assert!(close(p.distance(plane_z2()).unwrap(), 3.0));
+		assert!(close(Point::at(0.0, 0.0, 0.0).distance(plane_z2()).unwrap(), -2.0));
+
+		//	Distance to the z axis
+		assert!(close(p.distance(join([0.0, 0.0, 0.0], [0.0, 0.0, 4.0])).unwrap(), 5.0_f64.sqrt()));
+	}
+
+	#[test] fn test_line_distance() {
+		//	Skew: x axis and the line x = 0, z = 3 along y
+		let a = join([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]);
+		let b = join([0.0, 0.0, 3.0], [0.0, 1.0, 3.0]);
+		assert!(close(a.distance(b).unwrap(), 3.0));
+
+		//	Parallel, 5 apart
+		let c = join([0.0, 3.0, 4.0], [2.0, 3.0, 4.0]);
+		assert!(close(a.distance(c).unwrap(), 5.0));
+	}
+
+	//	Angles
+	#[test] fn test_angles() {
+		let (x, y) = (Plane::from_equation(1.0, 0.0, 0.0, 0.0), Plane::from_equation(1.0, 1.0, 0.0, -4.0));
+		assert!(close(x.angle(y), FRAC_PI_4));
+		assert!(close(x.angle(x * 3.0), 0.0));
+
+		let a = join([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]);
+		let b = join([0.0, 0.0, 3.0], [0.0, 1.0, 3.0]);
+		assert!(close(a.angle(b), FRAC_PI_2));
+		assert!(close(a.angle(-a), std::f64::consts::PI));
+	}
+
+	//	Projections
+	#[test] fn test_projections() {
+		let p = Point::at(1.0, 2.0, 5.0);
+		assert_point(p.project(plane_z2()).unwrap(), 1.0, 2.0, 2.0);
+		assert_point(p.project(join([0.0, 0.0, 0.0], [0.0, 0.0, 1.0])).unwrap(), 0.0, 0.0, 5.0);
+
+		//	Rejection is the direction from the projection back to the point
+		let r = p.reject(plane_z2()).unwrap();
+		assert!(close(r.e123, 0.0) && close(r.e021, 3.0));
+
+		//	Plane through a point, parallel to the original
+		let q = plane_z2().project(p).unwrap();
+		assert!(close(p.distance(q).unwrap(), 0.0) && close(q.angle(plane_z2()), 0.0));
+
+		//	Line through a point, parallel to the original
+		let l = join([0.0, 0.0, 0.0], [1.0, 1.0, 0.0]).project(p).unwrap();
+		assert!(close(p.distance(l).unwrap(), 0.0));
+		assert!(close(l.angle(join([0.0, 0.0, 0.0], [1.0, 1.0, 0.0])), 0.0));
+
+		//	Line onto plane lies in the plane and keeps its orientation
+		let l = join([0.0, 0.0, 0.0], [1.0, 0.0, 1.0]);
+		let lp = l.project(plane_z2()).unwrap();
+		assert!(matches!(lp.intersect(plane_z2()), Intersection::Coincident));
+		assert!(close(lp.angle(l), FRAC_PI_4));
+
+		//	Plane onto line contains the line and keeps its orientation
+		let x = Plane::from_equation(1.0, 2.0, -1.0, -4.0);
+		let xp = x.project(l).unwrap();
+		assert!(close(Point::at(3.0, 0.0, 3.0).distance(xp).unwrap(), 0.0));
+		assert!(x.angle(xp) < FRAC_PI_2);
+	}
+
+	#[test] fn test_same_grade_projections() {
+		//	A point onto a point is that point, and the rejection joins them
+		let p = Point::at(1.0, 2.0, 5.0);
+		assert_point(p.project(Point::at(0.0, 1.0, 0.0) * -3.0).unwrap(), 0.0, 1.0, 0.0);
+		let r = p.reject(Point::at(0.0, 1.0, 0.0)).unwrap();
+		assert!(close(r.e123, 0.0) && close(r.e032, 1.0) && close(r.e013, 1.0) && close(r.e021, 5.0));
+
+		//	Lines and planes land on the other one, turned to face the original
+		let (a, b) = (join([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]), join([0.0, 0.0, 3.0], [1.0, 1.0, 3.0]));
+		let ab = a.project(b).unwrap();
+		assert!(close(ab.angle(b), 0.0) && close(ab.distance(b).unwrap(), 0.0));
+		let ab = a.project(-b).unwrap();
+		assert!(close(ab.angle(b), 0.0));
+		let x = Plane::from_equation(1.0, 0.0, 1.0, 0.0);
+		let xz = x.project(plane_z2() * -1.0).unwrap();
+		assert!(close(xz.angle(plane_z2()), 0.0) && close(Point::at(0.0, 0.0, 2.0).distance(xz).unwrap(), 0.0));
+
+		//	Orthogonal ones leave nothing to land
+		assert_eq!(a.project(join([0.0, 0.0, 0.0], [0.0, 1.0, 0.0])), None);
+		assert_eq!(plane_z2().project(Plane::from_equation(1.0, 0.0, 0.0, 0.0)), None);
+	}
+
+	//	Intersections
+	#[test] fn test_plane_intersections() {
+		let x = Plane::from_equation(1.0, 0.0, 0.0, -1.0);
+		let Intersection::Proper(l) = x.intersect(plane_z2()) else { panic!() };
+		assert!(close(Point::at(1.0, 7.0, 2.0).distance(l).unwrap(), 0.0));
+
+		assert!(matches!(plane_z2().intersect(Plane::from_equation(0.0, 0.0, 1.0, 1.0)), Intersection::Ideal(_)));
+		assert!(matches!(plane_z2().intersect(plane_z2() * 0.5), Intersection::Coincident));
+	}
+
+	#[test] fn test_line_plane_intersections() {
+		let Intersection::Proper(p) = join([1.0, 1.0, 0.0], [1.0, 1.0, 1.0]).intersect(plane_z2()) else { panic!() };
+		assert_point(p, 1.0, 1.0, 2.0);
+
+		let Intersection::Ideal(d) = join([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]).intersect(plane_z2()) else { panic!() };
+		assert!(close(d.e032.abs(), 1.0));
+	}
+
+	#[test] fn test_line_line_intersections() {
+		let a = join([0.0, 0.0, 2.0], [1.0, 0.0, 2.0]);
+		let Intersection::Proper(p) = a.intersect(join([3.0, -1.0, 2.0], [3.0, 1.0, 2.0])) else { panic!() };
+		assert_point(p, 3.0, 0.0, 2.0);
+
+		assert!(matches!(a.intersect(join([0.0, 5.0, 2.0], [1.0, 5.0, 2.0])), Intersection::Ideal(_)));
+		assert!(matches!(a.intersect(join([0.0, 1.0, 7.0], [0.0, 2.0, 7.0])), Intersection::Disjoint));
+		assert!(matches!(a.intersect(a * 2.0), Intersection::Coincident));
+	}
+
+	//	Ideal inputs
+	fn at_infinity() -> Plane<f64> { Plane::new(1.0, 0.0, 0.0, 0.0) }
+	fn direction(x: f64, y: f64, z: f64) -> Point<f64> { Point::new(z, y, x, 0.0) }
+	//	Horizon of the planes with normal (x, y, z)
+	fn horizon(x: f64, y: f64, z: f64) -> Line<f64> { Line::new(x, y, z, 0.0, 0.0, 0.0) }
+
+	#[test] fn test_ideal_distances() {
+		let p = Point::at(1.0, 2.0, 5.0);
+		let x = join([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]);
+		assert_eq!(p.distance(direction(1.0, 0.0, 0.0)), None);
+		assert_eq!(direction(1.0, 0.0, 0.0).distance(plane_z2()), None);
+		assert_eq!(p.distance(at_infinity()), None);
+		assert_eq!(p.distance(horizon(0.0, 0.0, 1.0)), None);
+		assert_eq!(x.distance(horizon(0.0, 0.0, 1.0)), None);
+	}
+
+	#[test] fn test_ideal_intersections() {
+		//	A plane meets the plane at infinity in its horizon
+		let Intersection::Ideal(l) = plane_z2().intersect(at_infinity()) else { panic!() };
+		assert!(close(l.e03.abs(), 1.0) && close(l.norm(), 0.0));
+		assert!(matches!(at_infinity().intersect(at_infinity() * 2.0), Intersection::Coincident));
+
+		//	A line meets it in its direction
+		let x = join([0.0, 0.0, 0.0], [1.0, 0.0, 0.0]);
+		let Intersection::Ideal(d) = x.intersect(at_infinity()) else { panic!() };
+		assert!(close(d.e032.abs(), 1.0));
+
+		//	Horizons hold the directions parallel to their planes
+		let Intersection::Ideal(d) = horizon(0.0, 0.0, 1.0).intersect(x) else { panic!() };
+		assert!(close(d.e032.abs(), 1.0));
+		assert!(matches!(join([0.0, 0.0, 0.0], [0.0, 0.0, 1.0]).intersect(horizon(0.0, 0.0, 1.0)), Intersection::Disjoint));
+		let Intersection::Ideal(d) = horizon(0.0, 0.0, 2.0).intersect(horizon(3.0, 0.0, 0.0)) else { panic!() };
+		assert!(close(d.e013.abs(), 1.0));
+		assert!(matches!(horizon(0.0, 0.0, 1.0).intersect(horizon(0.0, 0.0, -2.0)), Intersection::Coincident));
+
+		//	A horizon lies in its own planes and crosses the others
+		assert!(matches!(horizon(0.0, 0.0, 1.0).intersect(plane_z2()), Intersection::Coincident));
+		let Intersection::Ideal(d) = horizon(0.0, 0.0, 1.0).intersect(Plane::from_equation(1.0, 0.0, 0.0, -3.0)) else { panic!() };
+		assert!(close(d.e013.abs(), 1.0));
+	}
+
+	#[test] fn test_ideal_fixed() {
+		//	Fixed point panics on division by zero, so no query may divide by a zero weight
+		use fixed::types::I48F16;
+		let f = I48F16::from_num;
+		let (o, one) = (f(0), f(1));
+		let p = Point::new(o, o, o, one);
+		let dir = Point::new(o, o, one, o);
+		let inf = Plane::new(one, o, o, o);
+		let ideal = Line::new(o, o, one, o, o, o);
+		let z = Line::new(o, o, o, one, o, o);
+
+		assert_eq!(p.distance(dir), None);
+		assert_eq!(p.distance(inf), None);
+		assert_eq!(z.distance(ideal), None);
+		assert!(matches!(Plane::new(o, o, o, one).intersect(inf), Intersection::Ideal(_)));
+		assert!(matches!(z.intersect(inf), Intersection::Ideal(_)));
+		assert!(matches!(ideal.intersect(z), Intersection::Disjoint));
+
+		assert_eq!(p.project(inf), None);
+		assert_eq!(dir.project(Plane::new(o, o, o, one)), None);
+		assert_eq!(p.reject(inf), None);
+		assert_eq!(dir.reject(Plane::new(o, o, o, one)), None);
+		assert_eq!(p.project(ideal), None);
+		assert_eq!(z.project(dir), None);
+		assert_eq!(inf.project(p), None);
+		assert_eq!(p.project(dir), None);
+		assert_eq!(Point::new(o, o, o, f(2)).project(p), Some(p));
+	}
+}
@@ -0,0 +1,72 @@
+//		Sandwich product
+//	`m.apply(x)` computes m x ~m without going through `PGA3D`: the product is
+//	expanded per input grade into one quadratic form of the motor per output
+//	blade. The formulas hold for any motor, not only unit ones. They round
+//	differently from the naive sandwich, so results match it to within 1e-12
+//	per component in f64 (what the tests check), not bit for bit.
+pub trait Sandwich<T> {
+	fn apply(&self, x: T) -> T;
+}
+
+//		Implementations
+//	Generated by build.rs from the symbolic expansion of m x ~m, for every
+//	versor and element pair listed there, alongside the other typed products in
+//	`pga3d_types`. So is `Motor::apply_direction`, which rotates an ideal point
+//	(direction): the expansion restricted to the motor's rotation part, so
+//	translation and any e123 weight are ignored.
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use super::super::pga3d_float::PGA3D;
+	use super::super::pga3d_types::{Plane, Line, Point, Motor, Rotor, Translator};
+
+	const EPS: f64 = 1e-12;
+
+	fn assert_close(got: PGA3D<f64>, expect: PGA3D<f64>) {
+		for i in 0..16 {
+			assert!((got[i] - expect[i]).abs() < EPS, "{} vs {}", got, expect);
+		}
+	}
+
+	//	Deliberately not normalised, the fast paths must not rely on it
+	fn motor() -> Motor<f64> { Motor::new(0.9, 0.3, -1.2, 0.8, 0.4, -0.25, 0.6, 0.15) }
+	fn rotor() -> Rotor<f64> { Rotor::new(0.9, 0.4, -0.25, 0.6) }
+	fn translator() -> Translator<f64> { Translator::new(1.1, 0.3, -1.2, 0.8) }
+
+	fn point() -> Point<f64> { Point::new(0.7, -2.0, 1.5, 1.3) }
+	fn line() -> Line<f64> { Line::new(0.2, 1.1, -0.6, -0.9, 0.35, 0.5) }
+	fn plane() -> Plane<f64> { Plane::new(-3.0, 0.4, 0.8, -0.2) }
+
+	//	Compare against m x ~m on full multivectors
+	macro_rules! check_sandwich {
+		($m:expr, $($x:expr),*) => { $({
+			let (m, x) = ($m, $x);
+			let naive = PGA3D::from(m) * PGA3D::from(x) * PGA3D::from(m).Reverse();
+			assert_close(m.apply(x).into(), naive);
+		})* };
+	}
+
+	//		Tests
+	#[test] fn test_motor_sandwich() { check_sandwich!(motor(), point(), line(), plane()); }
+	#[test] fn test_rotor_sandwich() { check_sandwich!(rotor(), point(), line(), plane()); }
+	#[test] fn test_translator_sandwich() { check_sandwich!(translator(), point(), line(), plane()); }
+
+	#[test] fn test_direction() {
+		let m = motor();
+		let d = Point::direction(0.3, -0.4, 1.2);
+		let naive = PGA3D::from(m) * PGA3D::from(d) * PGA3D::from(m).Reverse();
+		assert_close(m.apply_direction(d).into(), naive);
+
+		//	Translation must not move directions
+		let t = Motor::from(Translator::from_offset(5.0, 6.0, 7.0));
+		assert_eq!(t.apply_direction(d), d);
+	}
+
+	#[test] fn test_rigid_motion() {
+		let m = Motor::from(Translator::from_offset(0.0, 0.0, 5.0)) * Rotor::from_axis_angle(0.0, 0.0, 1.0, std::f64::consts::FRAC_PI_2);
+
+		//	Expected: (1, 0, 0) -> (0, 1, 5)
+		assert_close(m.apply(Point::at(1.0, 0.0, 0.0)).into(), Point::at(0.0, 1.0, 5.0).into());
+	}
+}
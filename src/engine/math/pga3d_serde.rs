@@ -0,0 +1,123 @@
+//  	Imports
+use std::fmt;
+use std::marker::PhantomData;
+
+use serde::de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor};
+use serde::ser::{Serialize, SerializeMap, SerializeTuple, Serializer};
+
+use super::scalar::Real;
+use super::pga3d_float::{PGA3D, basis, basis_count};
+
+//		Serialization
+//	Binary formats get all 16 coefficients in blade order, so fixed-point values
+//	travel as their raw bits. Human-readable formats get a map from blade name to
+//	coefficient with exact zeros left out, e.g. { "e032": "1", "e123": "1" }.
+impl<S: Real + Serialize> Serialize for PGA3D<S> {
+	fn serialize<Ser: Serializer>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error> {
+		if serializer.is_human_readable() {
+			let present: Vec<usize> = (0..basis_count).filter(|&i| self[i] != S::ZERO).collect();
+			let mut map = serializer.serialize_map(Some(present.len()))?;
+			for i in present {
+				map.serialize_entry(basis[i], &self[i])?;
+			}
+			map.end()
+		} else {
+			let mut tuple = serializer.serialize_tuple(basis_count)?;
+			for i in 0..basis_count {
+				tuple.serialize_element(&self[i])?;
+			}
+			tuple.end()
+		}
+	}
+}
+
+impl<'de, S: Real + Deserialize<'de>> Deserialize<'de> for PGA3D<S> {
+	fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+		if deserializer.is_human_readable() {
+			deserializer.deserialize_map(PGA3DVisitor(PhantomData))
+		} else {
+			deserializer.deserialize_tuple(basis_count, PGA3DVisitor(PhantomData))
+		}
+	}
+}
+
+struct PGA3DVisitor<S>(PhantomData<S>);
+
+impl<'de, S: Real + Deserialize<'de>> Visitor<'de> for PGA3DVisitor<S> {
+	type Value = PGA3D<S>;
+
+	fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "a PGA3D multivector as {} coefficients or a map of blade names", basis_count)
+	}
+
+	fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+		let mut res = PGA3D::zero();
+		for i in 0..basis_count {
+			res[i] = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(i, &self))?;
+		}
+		Ok(res)
+	}
+
+	fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+		let (mut res, mut seen) = (PGA3D::zero(), [false; basis_count]);
+		while let Some(name) = map.next_key::<String>()? {
+			let Some(i) = basis.iter().position(|&b| b == name) else {
+				return Err(de::Error::unknown_field(&name, basis));
+			};
+			if seen[i] {
+				return Err(de::Error::custom(format_args!("duplicate blade `{}`", name)));
+			}
+			seen[i] = true;
+			res[i] = map.next_value()?;
+		}
+		Ok(res)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use fixed::types::I48F16;
+
+	fn sample() -> PGA3D<I48F16> {
+		let mut res = PGA3D::zero();
+		res[0] = I48F16::lit("1.5");
+		res[5] = I48F16::from_bits(-7);
+		res[14] = I48F16::lit("-123456.25");
+		res
+	}
+
+	//		Tests
+	#[test] fn test_binary_is_lossless() {
+		let a = sample();
+		let bytes = bincode::serialize(&a).unwrap();
+		assert_eq!(bytes.len(), basis_count * 8);
+		assert_eq!(bincode::deserialize::<PGA3D<I48F16>>(&bytes).unwrap(), a);
+
+		let b = PGA3D::<f64>::point(0.1, -2.0, 1e300);
+		assert_eq!(bincode::deserialize::<PGA3D<f64>>(&bincode::serialize(&b).unwrap()).unwrap(), b);
+	}
+
+	#[test] fn test_human_readable_blades() {
+		let a = sample();
+		let json = serde_json::to_string(&a).unwrap();
+		assert_eq!(json, r#"{"1":"1.5","e01":"-0.0001","e123":"-123456.25"}"#);
+		assert_eq!(serde_json::from_str::<PGA3D<I48F16>>(&json).unwrap(), a);
+
+		let p: PGA3D<f64> = serde_json::from_str(r#"{ "e032": 2.0, "e123": 1.0 }"#).unwrap();
+		assert_eq!(p, PGA3D::point(2.0, 0.0, 0.0));
+	}
+
+	#[test] fn test_rejects_bad_blades() {
+		assert!(serde_json::from_str::<PGA3D<f64>>(r#"{ "e4": 1.0 }"#).is_err());
+		assert!(serde_json::from_str::<PGA3D<f64>>(r#"{ "e1": 1.0, "e1": 2.0 }"#).is_err());
+	}
+
+	//	Typed elements derive their encoding from their fields
+	#[test] fn test_typed_elements() {
+		use super::super::pga3d_types::Point;
+		let p = Point::<I48F16>::at(I48F16::lit("0.5"), I48F16::ZERO, I48F16::from_bits(1));
+		assert_eq!(bincode::deserialize::<Point<I48F16>>(&bincode::serialize(&p).unwrap()).unwrap(), p);
+		assert_eq!(serde_json::from_str::<Point<I48F16>>(&serde_json::to_string(&p).unwrap()).unwrap(), p);
+	}
+}
@@ -0,0 +1,390 @@
+//  	Imports
+use std::any::Any;
+
+use super::scalar::{Scalar, Real};
+use super::pga3d_float::{PGA3D, basis_count};
+
+//		SIMD products
+//	The kernel works in bitmask order, where blade index = set of basis vectors
+//	(bit 0 = e0 .. bit 3 = e3) written in ascending order. There e_A e_B = ± e_(A ^ B),
+//	so for a fixed left blade every output lane reads the right operand at a lane
+//	index XORed with a constant: the low bits are an in-register shuffle, the high
+//	bits pick the register. The whole product is 16 broadcast multiply-adds per
+//	register with constant sign vectors.
+//
+//	Wedge and dot are the grade-selected parts of the geometric product, so they
+//	run the same kernel with the discarded terms' signs zeroed. The vee takes
+//	e_A and e_B to e_~(~A ^ ~B) = e_(A ^ B ^ 15), which is the same XOR pattern
+//	once the right operand is stored reversed.
+//
+//	Sums run in a different order than the generated scalar code, so results agree
+//	to rounding, not bit for bit.
+
+//	Bitmask index of each `basis` blade, and whether `basis` lists it in the
+//	opposite orientation (e31, e021, e032)
+const MASK: [usize; basis_count] = [ 0,1,2,4,8,3,5,9,6,10,12,7,11,13,14,15 ];
+const FLIP: [bool; basis_count] = [ false,false,false,false,false,false,false,false,false,true,false,true,false,true,false,false ];
+
+//	Products, by index into the sign tables
+pub(crate) const GEOMETRIC: usize = 0;
+pub(crate) const WEDGE: usize = 1;
+pub(crate) const VEE: usize = 2;
+pub(crate) const DOT: usize = 3;
+
+//	Bitmask of the pseudoscalar, and what the vee XORs the right operand with
+const FULL: usize = basis_count - 1;
+const fn reversal(p: usize) -> usize { if p == VEE { FULL } else { 0 } }
+
+//	Sign of e_a e_b in bitmask order; zero when both contain the degenerate e0
+const fn product_sign(a: usize, b: usize) -> f64 {
+	if a & b & 1 != 0 { return 0.0; }
+
+	//	Swaps needed to sort the concatenated factors
+	let (mut swaps, mut j) = (0, 0);
+	while j < 4 {
+		if (b >> j) & 1 == 1 { swaps += (a >> (j + 1)).count_ones(); }
+		j += 1;
+	}
+	if swaps % 2 == 0 { 1.0 } else { -1.0 }
+}
+
+//	Sign of the complement e_~a, signed as in `codegen` so that conj(e_a) ^ J(e_a) = I
+const fn complement_sign(a: usize) -> f64 {
+	let g = a.count_ones();
+	let reverse = if (g * g.saturating_sub(1) / 2).is_multiple_of(2) { 1.0 } else { -1.0 };
+	let involute = if g.is_multiple_of(2) { 1.0 } else { -1.0 };
+	reverse * involute * product_sign(a, FULL ^ a)
+}
+
+//	Sign of the a b term of product `p`, landing in blade a ^ b (a ^ b ^ 15 for the vee)
+const fn term_sign(p: usize, a: usize, b: usize) -> f64 {
+	match p {
+		WEDGE if a & b != 0 => 0.0,
+		DOT if a & b != a && a & b != b => 0.0,
+		//	J⁻¹(J(a) ^ J(b)); the complements only meet if together they miss a vector
+		VEE if a | b != FULL => 0.0,
+		VEE => complement_sign(a) * complement_sign(b)
+			* product_sign(FULL ^ a, FULL ^ b) * complement_sign(FULL ^ a ^ b),
+		_ => product_sign(a, b),
+	}
+}
+
+//	SIGN[p][i][k]: sign of the a[i] b[i ^ k] term of output k of product `p`,
+//	with b reversed for the vee
+macro_rules! sign_table {
+	($T:ty) => {{
+		let mut table = [[[0.0 as $T; basis_count]; basis_count]; 4];
+		let mut p = 0;
+		while p < 4 {
+			let mut i = 0;
+			while i < basis_count {
+				let mut k = 0;
+				while k < basis_count {
+					table[p][i][k] = term_sign(p, i, i ^ k ^ reversal(p)) as $T;
+					k += 1;
+				}
+				i += 1;
+			}
+			p += 1;
+		}
+		table
+	}};
+}
+
+//		Lanes
+//	Minimal vector interface the kernel needs; `L` lanes of `T`, L <= 4.
+pub(crate) trait Lanes: Copy {
+	type T: Real;
+	const L: usize;
+	const SIGN: [[[Self::T; basis_count]; basis_count]; 4];
+
+	fn splat(x: Self::T) -> Self;
+	fn load(x: &[Self::T]) -> Self;
+	fn store(self, x: &mut [Self::T]);
+	fn add(self, b: Self) -> Self;
+	fn mul(self, b: Self) -> Self;
+
+	//	Lane l of the result is lane l ^ m of `self`
+	fn swap(self, m: usize) -> Self;
+}
+
+//	Portable fallback; plain arrays that the compiler is free to vectorise
+#[cfg(any(test, not(target_arch = "x86_64")))]
+pub(crate) mod portable {
+	use super::*;
+
+	#[derive(Clone, Copy)]
+	pub(crate) struct Array4<T>(pub [T; 4]);
+
+	macro_rules! impl_array_lanes {
+		($T:ty) => {
+			impl Lanes for Array4<$T> {
+				type T = $T;
+				const L: usize = 4;
+				const SIGN: [[[$T; basis_count]; basis_count]; 4] = sign_table!($T);
+
+				#[inline(always)] fn splat(x: $T) -> Self { Self([x; 4]) }
+				#[inline(always)] fn load(x: &[$T]) -> Self { Self([x[0], x[1], x[2], x[3]]) }
+				#[inline(always)] fn store(self, x: &mut [$T]) { x[..4].copy_from_slice(&self.0); }
+				#[inline(always)] fn add(self, b: Self) -> Self { Self(std::array::from_fn(|l| self.0[l] + b.0[l])) }
+				#[inline(always)] fn mul(self, b: Self) -> Self { Self(std::array::from_fn(|l| self.0[l] * b.0[l])) }
+				#[inline(always)] fn swap(self, m: usize) -> Self { Self(std::array::from_fn(|l| self.0[l ^ m])) }
+			}
+		};
+	}
+
+	impl_array_lanes!(f32);
+	impl_array_lanes!(f64);
+}
+
+//	SSE / SSE2. Both are part of the x86_64 baseline, which is all the `unsafe`
+//	register-only intrinsics below rely on; loads and stores also check lengths.
+#[cfg(target_arch = "x86_64")]
+pub(crate) mod sse {
+	use super::*;
+	use std::arch::x86_64::*;
+
+	#[derive(Clone, Copy)]
+	pub(crate) struct F32x4(__m128);
+
+	#[derive(Clone, Copy)]
+	pub(crate) struct F64x2(__m128d);
+
+	impl Lanes for F32x4 {
+		type T = f32;
+		const L: usize = 4;
+		const SIGN: [[[f32; basis_count]; basis_count]; 4] = sign_table!(f32);
+
+		#[inline(always)] fn splat(x: f32) -> Self { Self(unsafe { _mm_set1_ps(x) }) }
+		#[inline(always)] fn load(x: &[f32]) -> Self {
+			assert!(x.len() >= 4);
+			//	SAFETY: four readable floats, checked above; loadu has no alignment requirement
+			Self(unsafe { _mm_loadu_ps(x.as_ptr()) })
+		}
+		#[inline(always)] fn store(self, x: &mut [f32]) {
+			assert!(x.len() >= 4);
+			//	SAFETY: four writable floats, checked above
+			unsafe { _mm_storeu_ps(x.as_mut_ptr(), self.0) }
+		}
+		#[inline(always)] fn add(self, b: Self) -> Self { Self(unsafe { _mm_add_ps(self.0, b.0) }) }
+		#[inline(always)] fn mul(self, b: Self) -> Self { Self(unsafe { _mm_mul_ps(self.0, b.0) }) }
+		#[inline(always)] fn swap(self, m: usize) -> Self {
+			//	Immediates hold the source lane for each output lane, two bits each, lane 3 first
+			let v = self.0;
+			Self(unsafe { match m {
+				0 => v,
+				1 => _mm_shuffle_ps::<0b10_11_00_01>(v, v),
+				2 => _mm_shuffle_ps::<0b01_00_11_10>(v, v),
+				_ => _mm_shuffle_ps::<0b00_01_10_11>(v, v),
+			} })
+		}
+	}
+
+	impl Lanes for F64x2 {
+		type T = f64;
+		const L: usize = 2;
+		const SIGN: [[[f64; basis_count]; basis_count]; 4] = sign_table!(f64);
+
+		#[inline(always)] fn splat(x: f64) -> Self { Self(unsafe { _mm_set1_pd(x) }) }
+		#[inline(always)] fn load(x: &[f64]) -> Self {
+			assert!(x.len() >= 2);
+			//	SAFETY: two readable doubles, checked above; loadu has no alignment requirement
+			Self(unsafe { _mm_loadu_pd(x.as_ptr()) })
+		}
+		#[inline(always)] fn store(self, x: &mut [f64]) {
+			assert!(x.len() >= 2);
+			//	SAFETY: two writable doubles, checked above
+			unsafe { _mm_storeu_pd(x.as_mut_ptr(), self.0) }
+		}
+		#[inline(always)] fn add(self, b: Self) -> Self { Self(unsafe { _mm_add_pd(self.0, b.0) }) }
+		#[inline(always)] fn mul(self, b: Self) -> Self { Self(unsafe { _mm_mul_pd(self.0, b.0) }) }
+		#[inline(always)] fn swap(self, m: usize) -> Self {
+			if m & 1 == 0 { self } else { Self(unsafe { _mm_shuffle_pd::<1>(self.0, self.0) }) }
+		}
+	}
+}
+
+#[cfg(target_arch = "x86_64")]
+type LanesF32 = sse::F32x4;
+#[cfg(target_arch = "x86_64")]
+type LanesF64 = sse::F64x2;
+
+#[cfg(not(target_arch = "x86_64"))]
+type LanesF32 = portable::Array4<f32>;
+#[cfg(not(target_arch = "x86_64"))]
+type LanesF64 = portable::Array4<f64>;
+
+//		Kernel
+//	Expand `$body` once per blade with `$i` bound to a literal, so every register
+//	index and sign vector below is a constant and nothing spills to the stack.
+macro_rules! for_each_blade {
+	($i:ident => $body:block) => {
+		for_each_blade!(@ $i $body; 0 1 2 3 4 5 6 7 8 9 10 11 12 13 14 15);
+	};
+	(@ $i:ident $body:block; $($n:literal)*) => { $({ const $i: usize = $n; $body })* };
+}
+
+//	Product `P` of two multivectors in bitmask order
+#[inline(always)]
+fn kernel<V: Lanes, const P: usize>(a: &[V::T; basis_count], b: &[V::T; basis_count]) -> [V::T; basis_count] {
+	let (l, r) = (V::L, basis_count / V::L);
+
+	//	Every register of `b` under every in-register lane XOR
+	let mut bs = [[V::splat(V::T::ZERO); basis_count]; 4];
+	for q in 0..r {
+		let v = V::load(&b[q * l..]);
+		for (m, shuffled) in bs.iter_mut().enumerate().take(l) {
+			shuffled[q] = v.swap(m);
+		}
+	}
+
+	let mut acc = [V::splat(V::T::ZERO); basis_count];
+	for_each_blade!(I => {
+		let ai = V::splat(a[I]);
+		for q in 0..r {
+			let sign = V::load(&V::SIGN[P][I][q * l..]);
+			acc[q] = acc[q].add(ai.mul(sign).mul(bs[I % l][q ^ (I / l)]));
+		}
+	});
+
+	let mut res = [V::T::ZERO; basis_count];
+	for q in 0..r {
+		acc[q].store(&mut res[q * l..]);
+	}
+	res
+}
+
+//	Reorder into bitmask order, multiply, and reorder back
+#[inline(always)]
+pub(crate) fn product<V: Lanes, const P: usize>(a: &PGA3D<V::T>, b: &PGA3D<V::T>) -> PGA3D<V::T> {
+	let (mut x, mut y) = ([V::T::ZERO; basis_count], [V::T::ZERO; basis_count]);
+	for i in 0..basis_count {
+		x[MASK[i]] = if FLIP[i] { -a[i] } else { a[i] };
+		y[MASK[i] ^ reversal(P)] = if FLIP[i] { -b[i] } else { b[i] };
+	}
+
+	let z = kernel::<V, P>(&x, &y);
+
+	let mut res = PGA3D::zero();
+	for i in 0..basis_count {
+		res[i] = if FLIP[i] { -z[MASK[i]] } else { z[MASK[i]] };
+	}
+	res
+}
+
+//		Entry points
+//	Per product: f32 and f64 kernels, and a dispatch that returns `None` for every
+//	other scalar. The type checks are on constants and fold away after
+//	monomorphisation.
+macro_rules! entry_points {
+	($($P:ident: $f32:ident, $f64:ident, $any:ident;)*) => { $(
+		pub fn $f32(a: &PGA3D<f32>, b: &PGA3D<f32>) -> PGA3D<f32> { product::<LanesF32, $P>(a, b) }
+		pub fn $f64(a: &PGA3D<f64>, b: &PGA3D<f64>) -> PGA3D<f64> { product::<LanesF64, $P>(a, b) }
+
+		#[inline(always)]
+		pub fn $any<S: Real>(a: &PGA3D<S>, b: &PGA3D<S>) -> Option<PGA3D<S>> {
+			let (a, b): (&dyn Any, &dyn Any) = (a, b);
+			if let (Some(a), Some(b)) = (a.downcast_ref::<PGA3D<f32>>(), b.downcast_ref::<PGA3D<f32>>()) {
+				let res: &dyn Any = &$f32(a, b);
+				return res.downcast_ref::<PGA3D<S>>().copied();
+			}
+			if let (Some(a), Some(b)) = (a.downcast_ref::<PGA3D<f64>>(), b.downcast_ref::<PGA3D<f64>>()) {
+				let res: &dyn Any = &$f64(a, b);
+				return res.downcast_ref::<PGA3D<S>>().copied();
+			}
+			None
+		}
+	)* };
+}
+
+entry_points! {
+	GEOMETRIC: geometric_product_f32, geometric_product_f64, geometric_product;
+	WEDGE: wedge_product_f32, wedge_product_f64, wedge_product;
+	VEE: vee_product_f32, vee_product_f64, vee_product;
+	DOT: dot_product_f32, dot_product_f64, dot_product;
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use fixed::FixedI128;
+	use fixed::types::{I48F16, extra::U62};
+
+	type Wide = FixedI128<U62>;
+
+	fn sample<S: Real>(seed: f64) -> PGA3D<S> {
+		let mut res = PGA3D::zero();
+		for i in 0..basis_count {
+			res[i] = S::from_f64(((i as f64 + 1.0) * seed).sin());
+		}
+		res
+	}
+
+	//	The generated scalar path in 62 fractional bits; never routed through SIMD
+	fn reference(p: usize, sa: f64, sb: f64) -> PGA3D<Wide> {
+		let (a, b) = (sample::<Wide>(sa), sample::<Wide>(sb));
+		match p {
+			GEOMETRIC => a * b,
+			WEDGE => a ^ b,
+			VEE => a & b,
+			_ => a | b,
+		}
+	}
+
+	fn check<V: Lanes, const P: usize>(eps: f64) {
+		for (sa, sb) in [(0.7, 1.3), (0.11, 2.9), (1.7, -0.4), (3.1, 0.05)] {
+			let got = product::<V, P>(&sample(sa), &sample(sb));
+			let expect = reference(P, sa, sb);
+			for i in 0..basis_count {
+				let diff = (got[i].to_f64() - expect[i].to_f64()).abs();
+				assert!(diff < eps, "product {}, blade {} differs by {}", P, i, diff);
+			}
+		}
+	}
+
+	fn check_all<V: Lanes>(eps: f64) {
+		check::<V, GEOMETRIC>(eps);
+		check::<V, WEDGE>(eps);
+		check::<V, VEE>(eps);
+		check::<V, DOT>(eps);
+	}
+
+	//		Tests
+	#[test] fn test_sign_table() {
+		//	e1 e2 = e12, e2 e1 = -e12, e0 e0 = 0, e1 e1 = 1
+		assert_eq!(product_sign(2, 4), 1.0);
+		assert_eq!(product_sign(4, 2), -1.0);
+		assert_eq!(product_sign(1, 1), 0.0);
+		assert_eq!(product_sign(2, 2), 1.0);
+
+		//	e1 ^ e1 = 0, e1 | e12 = e2, e1 | e23 = 0, e123 & e1 = 0, e123 & e0123 = e123
+		assert_eq!(term_sign(WEDGE, 2, 2), 0.0);
+		assert_eq!(term_sign(DOT, 2, 6), 1.0);
+		assert_eq!(term_sign(DOT, 2, 12), 0.0);
+		assert_eq!(term_sign(VEE, 14, 2), 0.0);
+		assert_eq!(term_sign(VEE, 14, 15), 1.0);
+	}
+
+	#[test] fn test_portable_matches_scalar() {
+		check_all::<portable::Array4<f32>>(1e-5);
+		check_all::<portable::Array4<f64>>(1e-13);
+	}
+
+	#[cfg(target_arch = "x86_64")]
+	#[test] fn test_sse_matches_scalar() {
+		check_all::<sse::F32x4>(1e-5);
+		check_all::<sse::F64x2>(1e-13);
+	}
+
+	#[test] fn test_dispatch() {
+		let (a, b) = (sample::<f64>(0.7), sample::<f64>(1.3));
+		assert_eq!(geometric_product(&a, &b), Some(geometric_product_f64(&a, &b)));
+		assert_eq!(wedge_product(&a, &b), Some(wedge_product_f64(&a, &b)));
+		assert_eq!(vee_product(&a, &b), Some(vee_product_f64(&a, &b)));
+		assert_eq!(dot_product(&a, &b), Some(dot_product_f64(&a, &b)));
+
+		let (a, b) = (sample::<I48F16>(0.7), sample::<I48F16>(1.3));
+		assert_eq!(geometric_product(&a, &b), None);
+		assert_eq!(vee_product(&a, &b), None);
+	}
+}
@@ -0,0 +1,516 @@
+//  	Imports
+use std::fmt;
+use std::ops::{Neg, Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign, BitAnd, BitOr, BitXor};
+
+use bevy::reflect::Reflect;
+
+use super::scalar::Real;
+use super::pga3d_float::{PGA3D, basis, basis_count};
+use super::pga3d_sandwich::Sandwich;
+
+//		Strongly typed PGA3D elements
+//	Each type stores only the blades of its grade(s), so products between them
+//	skip every multiply that is known to vanish and return the right type.
+//
+//	Plane      = grade 1                (e0, e1, e2, e3)
+//	Line       = grade 2                (e01, e02, e03, e12, e31, e23)
+//	Point      = grade 3                (e021, e013, e032, e123)
+//	Motor      = even subalgebra        (1, grade 2, e0123)
+//	Rotor      = Euclidean even part    (1, e12, e31, e23)
+//	Translator = ideal even part        (1, e01, e02, e03)
+
+//		Errors
+//	Returned when narrowing a multivector that carries weight outside the target grade(s)
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GradeError {
+	pub blade: usize,
+	pub name: &'static str,
+}
+
+impl fmt::Display for GradeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		write!(f, "multivector has a non-zero {} component", self.name)
+	}
+}
+
+impl std::error::Error for GradeError {}
+
+//		Type macro
+macro_rules! define_pga_type {
+	//	1)	public-facing macro: the multivector type, then the element and its blade indices
+	($MV:ident => $Name:ident { $($field:ident: $idx:literal),* }) => {
+		define_pga_type!(@struct_def $MV => $Name { $($field: $idx),* });
+		define_pga_type!(@impl_ops $Name { $($field),* });
+		define_pga_type!(@impl_conv $MV => $Name { $($field: $idx),* });
+	};
+
+	//	2)	boilerplate: struct definition
+	(@struct_def $MV:ident => $Name:ident { $($field:ident: $idx:literal),* }) => {
+		#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+		#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+		pub struct $Name<S: Real> {
+			$(pub $field: S,)*
+		}
+
+		impl<S: Real> $Name<S> {
+			pub const ZERO: Self = Self { $($field: S::ZERO,)* };
+
+			//	Blade indices into the multivector, in field order
+			pub const BLADES: &'static [usize] = &[$($idx),*];
+
+			#[allow(clippy::too_many_arguments)]
+			#[inline(always)] pub const fn new($($field: S),*) -> Self { Self { $($field,)* } }
+		}
+
+		impl<S: Real> fmt::Display for $Name<S> {
+			fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+				fmt::Display::fmt(&$MV::from(*self), f)
+			}
+		}
+	};
+
+	//	3)	boilerplate: linear operations
+	(@impl_ops $Name:ident { $($field:ident),* }) => {
+		//	-Element
+		impl<S: Real> Neg for $Name<S> {
+			type Output = Self;
+			#[inline] fn neg(self) -> Self { Self { $($field: -self.$field,)* } }
+		}
+
+		//	Element +- Element
+		impl<S: Real> Add for $Name<S> {
+			type Output = Self;
+			#[inline] fn add(self, rhs: Self) -> Self { Self { $($field: self.$field + rhs.$field,)* } }
+		}
+		impl<S: Real> AddAssign for $Name<S> {
+			#[inline] fn add_assign(&mut self, rhs: Self) { $(self.$field += rhs.$field;)* }
+		}
+
+		impl<S: Real> Sub for $Name<S> {
+			type Output = Self;
+			#[inline] fn sub(self, rhs: Self) -> Self { Self { $($field: self.$field - rhs.$field,)* } }
+		}
+		impl<S: Real> SubAssign for $Name<S> {
+			#[inline] fn sub_assign(&mut self, rhs: Self) { $(self.$field -= rhs.$field;)* }
+		}
+
+		//	Element */ Scalar
+		impl<S: Real> Mul<S> for $Name<S> {
+			type Output = Self;
+			#[inline] fn mul(self, rhs: S) -> Self { Self { $($field: self.$field * rhs,)* } }
+		}
+		impl<S: Real> MulAssign<S> for $Name<S> {
+			#[inline] fn mul_assign(&mut self, rhs: S) { $(self.$field *= rhs;)* }
+		}
+
+		impl<S: Real> Div<S> for $Name<S> {
+			type Output = Self;
+			#[inline] fn div(self, rhs: S) -> Self { Self { $($field: self.$field / rhs,)* } }
+		}
+		impl<S: Real> DivAssign<S> for $Name<S> {
+			#[inline] fn div_assign(&mut self, rhs: S) { $(self.$field /= rhs;)* }
+		}
+	};
+
+	//	4)	boilerplate: lossless conversion to/from the full multivector
+	(@impl_conv $MV:ident => $Name:ident { $($field:ident: $idx:literal),* }) => {
+		impl<S: Real> From<$Name<S>> for $MV<S> {
+			#[inline] fn from(x: $Name<S>) -> Self {
+				let mut res = $MV::zero();
+				$(res[$idx] = x.$field;)*
+				res
+			}
+		}
+
+		impl<S: Real> TryFrom<$MV<S>> for $Name<S> {
+			type Error = GradeError;
+
+			fn try_from(mv: $MV<S>) -> Result<Self, GradeError> {
+				match (0..basis_count).find(|i| !Self::BLADES.contains(i) && mv[*i] != S::ZERO) {
+					Some(blade) => Err(GradeError { blade, name: basis[blade] }),
+					None => Ok(Self { $($field: mv[$idx],)* }),
+				}
+			}
+		}
+	};
+}
+
+//	Shared with the other algebras' element types
+pub(crate) use define_pga_type;
+
+//	Define types, and the products and sandwiches between them
+//	Generated by build.rs from the PGA3D signature and the element list there.
+include!(concat!(env!("OUT_DIR"), "/pga3d_elements.rs"));
+
+//		Constructors
+impl<S: Real> Plane<S> {
+	//	Homogeneous equation ax + by + cz + d = 0
+	#[inline] pub fn from_equation(a: S, b: S, c: S, d: S) -> Self { Self::new(d, a, b, c) }
+}
+
+impl<S: Real> Point<S> {
+	//	Euclidean point, e123 weight of one
+	#[inline] pub fn at(x: S, y: S, z: S) -> Self { Self::new(z, y, x, S::ONE) }
+
+	//	Ideal point (direction), no e123 weight
+	#[inline] pub fn direction(x: S, y: S, z: S) -> Self { Self::new(z, y, x, S::ZERO) }
+}
+
+impl<S: Real> Rotor<S> {
+	pub const IDENTITY: Self = Self { s: S::ONE, e12: S::ZERO, e31: S::ZERO, e23: S::ZERO };
+
+	//	Right-handed rotation by `angle` about the unit axis (x, y, z) through the origin
+	pub fn from_axis_angle(x: S, y: S, z: S, angle: S) -> Self {
+		let (sin, cos) = (angle / S::TWO).sin_cos();
+		Self::new(cos, -sin * z, -sin * y, -sin * x)
+	}
+
+	#[inline] pub fn reverse(self) -> Self { Self::new(self.s, -self.e12, -self.e31, -self.e23) }
+}
+
+impl<S: Real> Translator<S> {
+	pub const IDENTITY: Self = Self { s: S::ONE, e01: S::ZERO, e02: S::ZERO, e03: S::ZERO };
+
+	//	Translation by the offset (x, y, z)
+	pub fn from_offset(x: S, y: S, z: S) -> Self {
+		let h = -S::ONE / S::TWO;
+		Self::new(S::ONE, x * h, y * h, z * h)
+	}
+
+	#[inline] pub fn reverse(self) -> Self { Self::new(self.s, -self.e01, -self.e02, -self.e03) }
+}
+
+impl<S: Real> Motor<S> {
+	pub const IDENTITY: Self = Self {
+		s: S::ONE,
+		e01: S::ZERO, e02: S::ZERO, e03: S::ZERO,
+		e12: S::ZERO, e31: S::ZERO, e23: S::ZERO,
+		e0123: S::ZERO,
+	};
+
+	#[inline] pub fn reverse(self) -> Self {
+		Self::new(self.s, -self.e01, -self.e02, -self.e03, -self.e12, -self.e31, -self.e23, self.e0123)
+	}
+}
+
+impl<S: Real> Line<S> {
+	#[inline] pub fn reverse(self) -> Self { -self }
+}
+
+//		Norms
+//	Euclidean norm, as `PGA3D::norm`: the plane normal, the line direction,
+//	the point's e123 weight and the motor's rotor part.
+impl<S: Real> Plane<S> {
+	#[inline] pub fn norm(self) -> S { (self.e1 * self.e1 + self.e2 * self.e2 + self.e3 * self.e3).sqrt() }
+	#[inline] pub fn normalized(self) -> Self { self / self.norm() }
+}
+
+impl<S: Real> Line<S> {
+	#[inline] pub fn norm(self) -> S { (self.e12 * self.e12 + self.e31 * self.e31 + self.e23 * self.e23).sqrt() }
+	#[inline] pub fn normalized(self) -> Self { self / self.norm() }
+}
+
+impl<S: Real> Point<S> {
+	#[inline] pub fn norm(self) -> S { self.e123.abs() }
+
+	//	Scaled to an e123 weight of exactly one
+	#[inline] pub fn normalized(self) -> Self { self / self.e123 }
+}
+
+impl<S: Real> Motor<S> {
+	#[inline] pub fn norm(self) -> S {
+		(self.s * self.s + self.e12 * self.e12 + self.e31 * self.e31 + self.e23 * self.e23).sqrt()
+	}
+	#[inline] pub fn normalized(self) -> Self { self / self.norm() }
+
+	//	Full renormalisation: M M~ = 1 including its e0123 part, which `normalized`
+	//	leaves alone. Restores a rigid motion after drift from long integrations.
+	pub fn renormalized(self) -> Self {
+		let n = self * self.reverse();
+		let inv = S::ONE / n.s.sqrt();
+		self * Self::new(inv, S::ZERO, S::ZERO, S::ZERO, S::ZERO, S::ZERO, S::ZERO, -n.e0123 * inv / (S::TWO * n.s))
+	}
+
+	//	Half the motion of this (unit) motor: sqrt(M) sqrt(M) = M
+	pub fn sqrt(self) -> Self {
+		//	1 + M is degenerate for full turns, where the bivector route still works
+		if S::ONE + self.s <= S::EPS {
+			return Self::exp(self.log() / S::TWO);
+		}
+		(self + Self::IDENTITY).renormalized()
+	}
+}
+
+//	`normalized` without the division by zero (which panics for fixed-point)
+macro_rules! impl_try_normalized {
+	($($Name:ident),*) => { $(
+		impl<S: Real> $Name<S> {
+			#[inline] pub fn try_normalized(self) -> Option<Self> {
+				if self.norm() > S::EPS { Some(self.normalized()) } else { None }
+			}
+			#[inline] pub fn normalized_or_zero(self) -> Self { self.try_normalized().unwrap_or(Self::ZERO) }
+		}
+	)* };
+}
+
+impl_try_normalized!(Plane, Line, Point, Motor);
+
+//		Exponential and logarithm
+//	A motor acts by sandwiching, so exp(B) moves by *twice* B: a rotation of
+//	angle θ about a unit line L is exp(-θ/2 L), a twist ξ held for dt is exp(-dt/2 ξ).
+impl<S: Real> Motor<S> {
+	//	Exact screw motion generated by the bivector `b`
+	pub fn exp(b: Line<S>) -> Self {
+		//	Squared Euclidean (rotational) magnitude
+		let l = b.e12 * b.e12 + b.e31 * b.e31 + b.e23 * b.e23;
+		if l == S::ZERO {
+			return Self::new(S::ONE, b.e01, b.e02, b.e03, S::ZERO, S::ZERO, S::ZERO, S::ZERO);
+		}
+
+		//	Pitch term: the e0123 part of b^b / 2
+		let m = b.e01 * b.e23 + b.e02 * b.e31 + b.e03 * b.e12;
+		let a = l.sqrt();
+		let (sin, c) = a.sin_cos();
+		let s = sin / a;
+
+		//	t = m (cos a - sin a / a) / a², which cancels badly for tiny a
+		let t = if l < S::EPS {
+			m * (l * S::from_f64(1.0 / 30.0) - S::from_f64(1.0 / 3.0))
+		} else {
+			m / l * (c - s)
+		};
+
+		Self::new(
+			c,
+			s * b.e01 + t * b.e23, s * b.e02 + t * b.e31, s * b.e03 + t * b.e12,
+			s * b.e12, s * b.e31, s * b.e23,
+			m * s,
+		)
+	}
+
+	//	Bivector generating this (unit) motor; the inverse of `exp` for rotations below 2π
+	pub fn log(self) -> Line<S> {
+		//	Squared sine of the half-angle
+		let l = self.e12 * self.e12 + self.e31 * self.e31 + self.e23 * self.e23;
+		if l == S::ZERO {
+			return Line::new(self.e01, self.e02, self.e03, S::ZERO, S::ZERO, S::ZERO);
+		}
+
+		//	b = θ/sin θ, c = e0123 (1 - cos θ · b) / sin² θ
+		//	Both are ill-conditioned for tiny θ, so switch to their series there;
+		//	near a full turn (θ ≈ π) sin θ is tiny too, but the closed form holds.
+		let sin = l.sqrt();
+		let (b, c) = if l < S::EPS && self.s > S::ZERO {
+			(S::ONE + l * S::from_f64(1.0 / 6.0), self.e0123 * (S::from_f64(1.0 / 3.0) + l * S::from_f64(2.0 / 15.0)))
+		} else {
+			let b = sin.atan2(self.s) / sin;
+			(b, self.e0123 * (S::ONE - self.s * b) / l)
+		};
+
+		Line::new(
+			c * self.e23 + b * self.e01, c * self.e31 + b * self.e02, c * self.e12 + b * self.e03,
+			b * self.e12, b * self.e31, b * self.e23,
+		)
+	}
+
+	//	Constant world-frame twist that carries `self` onto `to` over `dt`
+	pub fn twist_to(self, to: Self, dt: S) -> Line<S> {
+		(to * self.reverse()).log() * (-S::TWO / dt)
+	}
+}
+
+//		Widening conversions
+impl<S: Real> From<Rotor<S>> for Motor<S> {
+	#[inline] fn from(r: Rotor<S>) -> Self {
+		Self::new(r.s, S::ZERO, S::ZERO, S::ZERO, r.e12, r.e31, r.e23, S::ZERO)
+	}
+}
+
+impl<S: Real> From<Translator<S>> for Motor<S> {
+	#[inline] fn from(t: Translator<S>) -> Self {
+		Self::new(t.s, t.e01, t.e02, t.e03, S::ZERO, S::ZERO, S::ZERO, S::ZERO)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	const EPS: f64 = 1e-12;
+
+	//	Fill every field with a distinct, non-trivial value
+	trait Sample { fn sample(seed: f64) -> Self; }
+	macro_rules! impl_sample {
+		($($Name:ident),*) => { $(
+			impl Sample for $Name<f64> {
+				fn sample(seed: f64) -> Self {
+					let mut mv = PGA3D::zero();
+					for (i, &idx) in Self::BLADES.iter().enumerate() {
+						mv[idx] = ((i as f64 + 1.0) * seed).sin();
+					}
+					Self::try_from(mv).unwrap()
+				}
+			}
+		)* };
+	}
+	impl_sample!(Plane, Line, Point, Motor, Rotor, Translator);
+
+	fn assert_close(got: PGA3D<f64>, expect: PGA3D<f64>) {
+		for i in 0..basis_count {
+			assert!((got[i] - expect[i]).abs() < EPS, "blade {} differs: {} vs {}", basis[i], got, expect);
+		}
+	}
+
+	//	Every typed product must agree with the full multivector product
+	macro_rules! check_product {
+		($op:tt, $A:ident, $B:ident) => {{
+			let (a, b) = ($A::<f64>::sample(0.7), $B::<f64>::sample(1.3));
+			let full = PGA3D::from(a) $op PGA3D::from(b);
+			assert_close(PGA3D::from(a $op b), full);
+		}};
+		($op:tt, $A:ident, $B:ident => scalar) => {{
+			let (a, b) = ($A::<f64>::sample(0.7), $B::<f64>::sample(1.3));
+			let full = PGA3D::from(a) $op PGA3D::from(b);
+			assert_close(PGA3D::new(a $op b, 0), full);
+		}};
+	}
+
+	//		Tests
+	//	Products
+	#[test] fn test_geometric_product() {
+		check_product!(*, Motor, Motor);
+		check_product!(*, Motor, Rotor);
+		check_product!(*, Rotor, Motor);
+		check_product!(*, Motor, Translator);
+		check_product!(*, Translator, Motor);
+		check_product!(*, Rotor, Rotor);
+		check_product!(*, Translator, Translator);
+		check_product!(*, Rotor, Translator);
+		check_product!(*, Translator, Rotor);
+		check_product!(*, Plane, Plane);
+		check_product!(*, Line, Line);
+		check_product!(*, Point, Point);
+	}
+
+	#[test] fn test_meet_join() {
+		check_product!(^, Plane, Plane);
+		check_product!(^, Plane, Line);
+		check_product!(^, Line, Plane);
+		check_product!(&, Point, Point);
+		check_product!(&, Point, Line);
+		check_product!(&, Line, Point);
+	}
+
+	#[test] fn test_inner_product() {
+		check_product!(|, Plane, Plane => scalar);
+		check_product!(|, Line, Line => scalar);
+		check_product!(|, Point, Point => scalar);
+		check_product!(|, Plane, Line);
+		check_product!(|, Line, Plane);
+		check_product!(|, Point, Line);
+		check_product!(|, Line, Point);
+		check_product!(|, Plane, Point);
+		check_product!(|, Point, Plane);
+	}
+
+	//	Conversion
+	#[test] fn test_roundtrip() {
+		let m = Motor::<f64>::sample(0.3);
+		assert_eq!(Motor::try_from(PGA3D::from(m)), Ok(m));
+
+		let p = PGA3D::<f64>::point(1.0, 2.0, 3.0);
+		assert_eq!(Point::try_from(p), Ok(Point::at(1.0, 2.0, 3.0)));
+		assert_eq!(Plane::try_from(p), Err(GradeError { blade: 11, name: "e021" }));
+	}
+
+	//	Constructors
+	#[test] fn test_rigid_motion() {
+		let r = Rotor::<f64>::from_axis_angle(0.0, 0.0, 1.0, std::f64::consts::FRAC_PI_2);
+		let t = Translator::<f64>::from_offset(0.0, 0.0, 5.0);
+		let m: PGA3D<f64> = (t * r).into();
+
+		//	Expected: (1, 0, 0) -> (0, 1, 5)
+		let res = m * PGA3D::from(Point::at(1.0, 0.0, 0.0)) * m.Reverse();
+		assert_close(res, PGA3D::point(0.0, 1.0, 5.0));
+	}
+
+	//	Norms
+	#[test] fn test_renormalized() {
+		let m = Motor::exp(screw()) * Motor::from(Rotor::from_axis_angle(1.0, 0.0, 0.0, 0.3));
+		let drift = Motor::new(0.01, 0.02, -0.01, 0.03, -0.02, 0.01, 0.01, 0.04);
+		let r = (m + drift).renormalized();
+
+		let n = r * r.reverse();
+		assert_close(n.into(), Motor::<f64>::IDENTITY.into());
+		assert!((PGA3D::from(r) - PGA3D::from(m)).norm() < 0.1);
+	}
+
+	#[test] fn test_motor_sqrt() {
+		let m = Motor::exp(screw() * 2.0);
+		let h = m.sqrt();
+		assert_close((h * h).into(), m.into());
+		assert_close(h.into(), Motor::exp(screw()).into());
+
+		//	A full turn about z takes the fallback
+		let turn = Motor::from(Rotor::from_axis_angle(0.0, 0.0, 1.0, 2.0 * std::f64::consts::PI - 1e-9));
+		let h = turn.sqrt();
+		assert_close((h * h).into(), turn.into());
+	}
+
+	#[test] fn test_try_normalized() {
+		use fixed::types::I48F16;
+		assert_eq!(Line::<I48F16>::ZERO.try_normalized(), None);
+		assert_eq!(Point::<I48F16>::ZERO.normalized_or_zero(), Point::ZERO);
+		assert_eq!(Plane::from_equation(0.0, 2.0, 0.0, 1.0).try_normalized(), Some(Plane::from_equation(0.0, 1.0, 0.0, 0.5)));
+	}
+
+	//	Exponential and logarithm
+	fn screw() -> Line<f64> { Line::new(0.3, -1.2, 0.8, 0.4, -0.25, 0.6) }
+
+	#[test] fn test_exp_rotation() {
+		let theta = 1.1;
+		let m = Motor::exp(Line::new(0.0, 0.0, 0.0, 1.0, 0.0, 0.0) * (-theta / 2.0));
+		let r = Rotor::from_axis_angle(0.0, 0.0, 1.0, theta);
+		assert_close(m.into(), r.into());
+	}
+
+	#[test] fn test_exp_series() {
+		//	exp(B) = sum B^k / k!
+		let b = PGA3D::from(screw());
+		let (mut term, mut sum) = (PGA3D::zero() + 1.0, PGA3D::zero() + 1.0);
+		for k in 1..40 {
+			term = term * b * (1.0 / k as f64);
+			sum += term;
+		}
+		assert_close(Motor::exp(screw()).into(), sum);
+	}
+
+	#[test] fn test_log_inverts_exp() {
+		for b in [screw(), screw() * 1e-4, screw() * 1e-9, Line::new(2.0, -3.0, 0.5, 0.0, 0.0, 0.0)] {
+			assert_close(Motor::exp(b).log().into(), b.into());
+		}
+	}
+
+	#[test] fn test_exp_integrates_twist() {
+		//	Ten steps of a constant twist land exactly where one big step does
+		let (xi, dt) = (screw(), 0.05);
+		let step = Motor::exp(xi * (-dt / 2.0));
+		let mut pose = Motor::IDENTITY;
+		for _ in 0..10 { pose = step * pose; }
+		assert_close(pose.into(), Motor::exp(xi * (-dt * 5.0)).into());
+		assert_close(Motor::IDENTITY.twist_to(pose, dt * 10.0).into(), xi.into());
+	}
+
+	#[test] fn test_exp_log_fixed() {
+		use fixed::types::I48F16;
+		let b = screw();
+		let bf = Line::<I48F16>::new(
+			I48F16::from_num(b.e01), I48F16::from_num(b.e02), I48F16::from_num(b.e03),
+			I48F16::from_num(b.e12), I48F16::from_num(b.e31), I48F16::from_num(b.e23),
+		);
+		let res = Motor::exp(bf).log();
+		for (got, expect) in [(res.e01, b.e01), (res.e12, b.e12), (res.e23, b.e23)] {
+			assert!((got.to_num::<f64>() - expect).abs() < 1e-3, "{} vs {}", got, expect);
+		}
+	}
+}
@@ -0,0 +1,194 @@
+//  	Imports
+use std::fmt::{Debug, Display};
+use std::ops::{Neg, Add, AddAssign, Sub, SubAssign, Mul, MulAssign, Div, DivAssign};
+
+use fixed::FixedI128;
+use fixed::types::{I2F62, I48F16, extra::U62};
+
+use super::fixmath;
+
+//		Abstractions
+//	Scalar
+//	Everything the math modules need from a number, so vectors, PGA and orbits
+//	are written once and instantiated per precision. Shared by the PGA modules
+//	(through `Real`) and the fixed-point vectors (`TypeVec3::Scalar`).
+pub trait Scalar:
+	//	Rust traits
+	Clone + Copy + Debug + Display + Default + PartialEq + PartialOrd
+	+ Send + Sync + 'static
+	//	Algebraic traits
+	+ Neg<Output = Self>
+	+ Add<Output = Self> + AddAssign
+	+ Sub<Output = Self> + SubAssign
+	+ Mul<Output = Self> + MulAssign
+	+ Div<Output = Self> + DivAssign
+{
+	//	Constants
+	const ZERO: Self;
+	const ONE: Self;
+	//	Tolerance for comparisons that absorb rounding, not the type's resolution
+	const EPS: Self;
+
+	//	Conversion; lossy in general, see `Widen` for the exact ones
+	fn from_f64(v: f64) -> Self;
+	fn to_f64(self) -> f64;
+
+	//	Elementary functions
+	fn abs(self) -> Self;
+	fn sqrt(self) -> Self;
+	fn sin_cos(self) -> (Self, Self);
+}
+
+//	Real
+//	Scalars that span 2 and π, as rotors, sandwiches and angles need. All but
+//	I2F62, whose [-2, 2) range is for unit quantities (quaternion components,
+//	sines).
+pub trait Real: Scalar {
+	const TWO: Self;
+	const PI: Self;
+
+	//	Angle of the point (x, self), in [-π, π]: here rather than on `Scalar`,
+	//	as it needs the range of π
+	fn atan2(self, x: Self) -> Self;
+}
+
+//	Widen
+//	Conversion that is exact for every value of `Self`: each scalar into itself,
+//	f32 into f64, and the narrower fixed-point types into FixedI128<U62>.
+pub trait Widen<T: Scalar>: Scalar {
+	fn widen(self) -> T;
+}
+
+//		Implementations
+//	Floating-point
+macro_rules! impl_scalar_float {
+	($T:ty) => {
+		impl Scalar for $T {
+			const ZERO: Self = 0.0;
+			const ONE: Self = 1.0;
+			const EPS: Self = 0.00001;
+
+			#[inline] fn from_f64(v: f64) -> Self { v as $T }
+			#[inline] fn to_f64(self) -> f64 { self as f64 }
+
+			#[inline] fn abs(self) -> Self { <$T>::abs(self) }
+			#[inline] fn sqrt(self) -> Self { <$T>::sqrt(self) }
+			#[inline] fn sin_cos(self) -> (Self, Self) { <$T>::sin_cos(self) }
+		}
+
+		impl Real for $T {
+			const TWO: Self = 2.0;
+			const PI: Self = std::f64::consts::PI as $T;
+
+			#[inline] fn atan2(self, x: Self) -> Self { <$T>::atan2(self, x) }
+		}
+	};
+}
+
+impl_scalar_float!(f32);
+impl_scalar_float!(f64);
+
+//	Fixed-point
+macro_rules! impl_scalar_fixed {
+	($T:ty, $Eps:expr) => {
+		impl Scalar for $T {
+			const ZERO: Self = <$T>::ZERO;
+			const ONE: Self = <$T>::ONE;
+			const EPS: Self = $Eps;
+
+			#[inline] fn from_f64(v: f64) -> Self { <$T>::from_num(v) }
+			#[inline] fn to_f64(self) -> f64 { self.to_num() }
+
+			#[inline] fn abs(self) -> Self { <$T>::abs(self) }
+			//	Rounded to nearest, where `fixed` truncates
+			#[inline] fn sqrt(self) -> Self { fixmath::sqrt(self) }
+
+			//	Deterministic
+			#[inline] fn sin_cos(self) -> (Self, Self) { fixmath::sin_cos(self) }
+		}
+	};
+}
+
+macro_rules! impl_real_fixed {
+	($T:ty) => {
+		impl Real for $T {
+			const TWO: Self = <$T>::lit("2");
+			const PI: Self = <$T>::PI;
+
+			//	Deterministic, and π fits every `Real`
+			#[inline] fn atan2(self, x: Self) -> Self { fixmath::atan2(self, x).expect("angles in [-π, π] fit a `Real`") }
+		}
+	};
+}
+
+impl_scalar_fixed!(I48F16, I48F16::DELTA);
+impl_scalar_fixed!(FixedI128<U62>, FixedI128::<U62>::lit("0.00001"));
+impl_scalar_fixed!(I2F62, I2F62::lit("0.000000000000001"));
+
+impl_real_fixed!(I48F16);
+impl_real_fixed!(FixedI128<U62>);
+
+//	Lossless conversions
+macro_rules! impl_widen {
+	($($From:ty => $To:ty: |$v:ident| $e:expr;)*) => {
+		$(impl Widen<$To> for $From {
+			#[inline] fn widen(self) -> $To { let $v = self; $e }
+		})*
+	};
+}
+
+impl_widen! {
+	f32 => f32: |v| v;
+	f64 => f64: |v| v;
+	I48F16 => I48F16: |v| v;
+	I2F62 => I2F62: |v| v;
+	FixedI128<U62> => FixedI128<U62>: |v| v;
+
+	f32 => f64: |v| v as f64;
+	I48F16 => FixedI128<U62>: |v| FixedI128::<U62>::from_num(v);
+	I2F62 => FixedI128<U62>: |v| FixedI128::<U62>::from_num(v);
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+
+	type Wide = FixedI128<U62>;
+
+	//	Written once against the trait, instantiated per precision
+	fn hypot<S: Scalar>(a: S, b: S) -> S { (a * a + b * b).sqrt() }
+	fn unit_angle<S: Real>(a: S, b: S) -> S { S::atan2(b / hypot(a, b), a / hypot(a, b)) }
+
+	#[test] fn test_generic() {
+		assert_eq!(hypot(3.0f32, 4.0), 5.0);
+		assert_eq!(hypot(3.0f64, 4.0), 5.0);
+		assert_eq!(hypot(I48F16::from_num(3), I48F16::from_num(4)), I48F16::from_num(5));
+		assert_eq!(hypot(Wide::from_num(3), Wide::from_num(4)), Wide::from_num(5));
+		//	0.6 and 0.8 come from f64, good to ~2^-54
+		let one = hypot(I2F62::from_num(0.6), I2F62::from_num(0.8));
+		assert!((one - I2F62::ONE).abs() < I2F62::EPS);
+
+		let quarter = unit_angle(Wide::from_num(0.5), Wide::from_num(0.5));
+		assert!((quarter.to_f64() - std::f64::consts::FRAC_PI_4).abs() < 1e-15);
+		assert!((unit_angle(1.0f64, 0.0) - 0.0).abs() < f64::EPS);
+	}
+
+	#[test] fn test_i2f62_range() {
+		//	Not a `Real`: atan2 past the ±2 range has no value, rather than a
+		//	saturated one
+		assert_eq!(fixmath::atan2(I2F62::ZERO, -I2F62::ONE), None);
+		let (s, c) = I2F62::from_num(1.5).sin_cos();
+		assert!((s.to_f64() - 1.5f64.sin()).abs() < 1e-15 && (c.to_f64() - 1.5f64.cos()).abs() < 1e-15);
+	}
+
+	#[test] fn test_widen() {
+		let third = I48F16::ONE / I48F16::from_num(3);
+		let wide: Wide = third.widen();
+		assert_eq!(wide.to_bits() >> 46, third.to_bits() as i128);
+		assert_eq!(Widen::<Wide>::widen(I48F16::MAX).to_bits() >> 46, I48F16::MAX.to_bits() as i128);
+
+		let unit = I2F62::MIN;
+		assert_eq!(Widen::<Wide>::widen(unit).to_bits(), unit.to_bits() as i128);
+		assert_eq!(Widen::<f64>::widen(0.1f32), 0.1f32 as f64);
+	}
+}
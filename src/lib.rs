@@ -0,0 +1,10 @@
+//  Modules
+pub mod engine;
+
+//	The build script's code generator, compiled into the tests so they cover it
+#[cfg(test)]
+#[path = "../codegen"]
+mod codegen {
+	#[allow(dead_code)] pub mod algebra;
+	#[allow(dead_code)] pub mod emit;
+}
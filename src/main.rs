@@ -1,10 +1,13 @@
 //  Imports
 use bevy::prelude::*;
 
+use hardsci_sandbox::engine::frame::{FramePropagationPlugin, FloatingOriginPlugin};
+
 //  Main function
 fn main() {
 	let mut app = App::new();
 	app.add_plugins(DefaultPlugins);
+	app.add_plugins((FramePropagationPlugin, FloatingOriginPlugin));
 
 	app.run();
 }